@@ -0,0 +1,342 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::difficulty::{Difficulty, GameSettings};
+use crate::network::DEFAULT_ADDRESS;
+use crate::stats::DEFAULT_STATS_FILE;
+
+#[derive(Debug, Default, PartialEq)]
+pub enum Mode {
+  // Single player, against the computer
+  #[default]
+  Local,
+  // Two players over TCP: the host picks the number, the other one joins and guesses it
+  Host(String),
+  Join(String),
+  // The computer plays against itself
+  Auto,
+  // The player and the computer guess the same secret number; fewer attempts wins
+  Race,
+  // Print the statistics of the games recorded so far, without playing
+  Stats,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Variant {
+  // Guess a number
+  #[default]
+  Numbers,
+  // Guess a word, getting feedback for each letter
+  Words,
+}
+
+impl Variant {
+  pub const ALL: [Variant; 2] = [Variant::Numbers, Variant::Words];
+}
+
+// As written in the statistics file
+impl fmt::Display for Variant {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let name = match self {
+      Variant::Numbers => "numbers",
+      Variant::Words => "words",
+    };
+    write!(f, "{name}")
+  }
+}
+
+impl FromStr for Variant {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Variant, String> {
+    match s {
+      "numbers" => Ok(Variant::Numbers),
+      "words" => Ok(Variant::Words),
+      other => Err(format!("unknown variant '{other}' (expected numbers or words)")),
+    }
+  }
+}
+
+#[derive(Default)]
+pub struct Config {
+  pub mode: Mode,
+  pub variant: Variant,
+  // `None` means that the player has to pick one from the menu
+  pub difficulty: Option<Difficulty>,
+  // Explicit values override the ones of the chosen difficulty
+  pub min: Option<u32>,
+  pub max: Option<u32>,
+  pub max_attempts: Option<u32>,
+  // In seconds
+  pub time_limit: Option<u32>,
+  pub debug: bool,
+  // Tell the player whether each guess is closer to the secret number than the previous one
+  pub warmer_colder: bool,
+  // Print one line per event instead of redrawing the screen
+  pub plain: bool,
+  // Where finished games are recorded. `None` means the default file
+  pub stats_file: Option<String>,
+}
+
+impl Config {
+  // Takes ownership of any iterator of Strings (e.g. `std::env::args()`), so tests can pass a plain vector
+  pub fn build(args: impl Iterator<Item = String>) -> Result<Config, String> {
+    // The first argument is always the program name.
+    // `peekable` allows looking at the next argument without consuming it (see `host` and `join`)
+    let mut args = args.skip(1).peekable();
+
+    let mut config = Config::default();
+
+    while let Some(arg) = args.next() {
+      match arg.as_str() {
+        "--min" => config.min = Some(parse_number("--min", args.next())?),
+        "--max" => config.max = Some(parse_number("--max", args.next())?),
+        "--range" => {
+          let (min, max) = parse_range(args.next())?;
+          config.min = Some(min);
+          config.max = Some(max);
+        }
+        "host" | "join" => {
+          // The address is optional
+          let address = args
+            .next_if(|next| !next.starts_with("--"))
+            .unwrap_or(String::from(DEFAULT_ADDRESS));
+          config.set_mode(if arg == "host" { Mode::Host(address) } else { Mode::Join(address) })?;
+        }
+        "stats" => config.set_mode(Mode::Stats)?,
+        "--stats-file" => config.stats_file = Some(args.next().ok_or("--stats-file expects a path")?),
+        "--auto" => config.set_mode(Mode::Auto)?,
+        "--race" => config.set_mode(Mode::Race)?,
+        "--words" => config.variant = Variant::Words,
+        "--debug" => config.debug = true,
+        "--hints" => config.warmer_colder = true,
+        "--plain" => config.plain = true,
+        "--max-attempts" => config.max_attempts = Some(parse_number("--max-attempts", args.next())?),
+        "--time-limit" => config.time_limit = Some(parse_number("--time-limit", args.next())?),
+        "--difficulty" => {
+          let value = args.next().ok_or("--difficulty expects easy, normal or hard")?;
+          config.difficulty = Some(value.parse()?);
+        }
+        other => return Err(format!("unknown argument '{other}'")),
+      }
+    }
+
+    if let (Some(min), Some(max)) = (config.min, config.max) {
+      check_range(min, max)?;
+    }
+
+    if config.max_attempts == Some(0) {
+      return Err(String::from("--max-attempts must be at least 1"));
+    }
+
+    if config.time_limit == Some(0) {
+      return Err(String::from("--time-limit must be at least 1 second"));
+    }
+
+    if config.variant == Variant::Words && (config.mode != Mode::Local || config.warmer_colder) {
+      return Err(String::from("--words can't be combined with host, join, stats, --auto, --race or --hints"));
+    }
+
+    Ok(config)
+  }
+
+  pub fn stats_file(&self) -> &str {
+    self.stats_file.as_deref().unwrap_or(DEFAULT_STATS_FILE)
+  }
+
+  // Whether the games are played with something else than the preset of the chosen difficulty.
+  // Their results can't be compared with the ones of the preset, so the statistics keep them apart
+  pub fn has_overrides(&self) -> bool {
+    self.min.is_some() || self.max.is_some() || self.max_attempts.is_some() || self.time_limit.is_some() || self.warmer_colder
+  }
+
+  fn set_mode(&mut self, mode: Mode) -> Result<(), String> {
+    if self.mode != Mode::Local {
+      return Err(String::from("only one of host, join, stats, --auto and --race can be used"));
+    }
+    self.mode = mode;
+    Ok(())
+  }
+
+  // Combines the preset of `difficulty` with the values given explicitly in the command line
+  pub fn settings(&self, difficulty: Difficulty) -> Result<GameSettings, String> {
+    let preset = difficulty.settings();
+    let settings = GameSettings {
+      min: self.min.unwrap_or(preset.min),
+      max: self.max.unwrap_or(preset.max),
+      max_attempts: self.max_attempts.or(preset.max_attempts),
+      hints: preset.hints,
+      time_limit: self
+        .time_limit
+        .map(|seconds| Duration::from_secs(seconds.into()))
+        .or(preset.time_limit),
+    };
+    check_range(settings.min, settings.max)?;
+
+    Ok(settings)
+  }
+}
+
+fn check_range(min: u32, max: u32) -> Result<(), String> {
+  if min >= max {
+    return Err(format!("min ({min}) must be smaller than max ({max})"));
+  }
+  Ok(())
+}
+
+fn parse_number(flag: &str, value: Option<String>) -> Result<u32, String> {
+  let value = value.ok_or(format!("{flag} expects a number"))?;
+  value
+    .trim()
+    .parse()
+    .map_err(|_| format!("{flag} expects a number, but got '{value}'"))
+}
+
+// Accepts ranges written as "<min>..<max>", e.g. "1..1000"
+fn parse_range(value: Option<String>) -> Result<(u32, u32), String> {
+  let value = value.ok_or("--range expects a value like 1..1000")?;
+  match value.split_once("..") {
+    Some((min, max)) => Ok((
+      parse_number("--range", Some(min.to_string()))?,
+      parse_number("--range", Some(max.to_string()))?,
+    )),
+    None => Err(format!("--range expects a value like 1..1000, but got '{value}'")),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn build(args: &[&str]) -> Result<Config, String> {
+    let args = std::iter::once("guessing-game").chain(args.iter().copied());
+    Config::build(args.map(String::from))
+  }
+
+  #[test]
+  fn uses_the_preset_when_nothing_is_overridden() {
+    let config = build(&[]).unwrap();
+    assert_eq!(config.mode, Mode::Local);
+    assert_eq!(config.variant, Variant::Numbers);
+    assert_eq!(config.difficulty, None);
+    assert!(!config.debug);
+    assert!(!config.warmer_colder);
+    assert!(!config.plain);
+    assert_eq!(config.settings(Difficulty::Normal), Ok(Difficulty::Normal.settings()));
+  }
+
+  #[test]
+  fn reads_min_max_and_debug() {
+    let config = build(&["--min", "10", "--max", "20", "--debug"]).unwrap();
+    assert_eq!(config.settings(Difficulty::Normal).unwrap().range(), 10..=20);
+    assert!(config.debug);
+  }
+
+  #[test]
+  fn reads_range() {
+    let config = build(&["--range", "1..1000"]).unwrap();
+    assert_eq!(config.settings(Difficulty::Easy).unwrap().range(), 1..=1000);
+  }
+
+  #[test]
+  fn reads_max_attempts() {
+    let config = build(&["--max-attempts", "7"]).unwrap();
+    assert_eq!(config.settings(Difficulty::Easy).unwrap().max_attempts, Some(7));
+    assert!(build(&["--max-attempts", "0"]).is_err());
+  }
+
+  #[test]
+  fn reads_time_limit() {
+    let config = build(&["--time-limit", "30"]).unwrap();
+    assert_eq!(config.settings(Difficulty::Normal).unwrap().time_limit, Some(Duration::from_secs(30)));
+    assert!(build(&["--time-limit", "0"]).is_err());
+  }
+
+  #[test]
+  fn reads_hints() {
+    assert!(build(&["--hints"]).unwrap().warmer_colder);
+  }
+
+  #[test]
+  fn reads_plain() {
+    assert!(build(&["--plain"]).unwrap().plain);
+  }
+
+  #[test]
+  fn reads_difficulty() {
+    let config = build(&["--difficulty", "hard"]).unwrap();
+    assert_eq!(config.difficulty, Some(Difficulty::Hard));
+    assert!(build(&["--difficulty", "nightmare"]).is_err());
+  }
+
+  #[test]
+  fn reads_network_modes() {
+    let config = build(&["host", "--difficulty", "easy"]).unwrap();
+    assert_eq!(config.mode, Mode::Host(String::from(DEFAULT_ADDRESS)));
+    assert_eq!(config.difficulty, Some(Difficulty::Easy));
+
+    let config = build(&["join", "192.168.1.2:9000"]).unwrap();
+    assert_eq!(config.mode, Mode::Join(String::from("192.168.1.2:9000")));
+  }
+
+  #[test]
+  fn reads_solver_modes() {
+    assert_eq!(build(&["--auto"]).unwrap().mode, Mode::Auto);
+    assert_eq!(build(&["--race"]).unwrap().mode, Mode::Race);
+  }
+
+  #[test]
+  fn reads_stats() {
+    let config = build(&["stats"]).unwrap();
+    assert_eq!(config.mode, Mode::Stats);
+    assert_eq!(config.stats_file(), DEFAULT_STATS_FILE);
+
+    let config = build(&["stats", "--stats-file", "games.csv"]).unwrap();
+    assert_eq!(config.stats_file(), "games.csv");
+    assert!(build(&["--stats-file"]).is_err());
+  }
+
+  #[test]
+  fn detects_overrides_of_the_preset() {
+    assert!(!build(&["--difficulty", "hard", "--plain"]).unwrap().has_overrides());
+    assert!(build(&["--difficulty", "hard", "--range", "1..10"]).unwrap().has_overrides());
+    assert!(build(&["--max-attempts", "3"]).unwrap().has_overrides());
+    assert!(build(&["--hints"]).unwrap().has_overrides());
+  }
+
+  #[test]
+  fn rejects_several_modes() {
+    assert!(build(&["--auto", "--race"]).is_err());
+    assert!(build(&["host", "--auto"]).is_err());
+  }
+
+  #[test]
+  fn reads_word_variant() {
+    assert_eq!(build(&["--words"]).unwrap().variant, Variant::Words);
+    assert!(build(&["--words", "--race"]).is_err());
+    assert!(build(&["--words", "--hints"]).is_err());
+  }
+
+  #[test]
+  fn rejects_min_not_smaller_than_max() {
+    let err = build(&["--min", "50", "--max", "50"]).err().unwrap();
+    assert!(err.contains("must be smaller than"), "unexpected error: {err}");
+  }
+
+  #[test]
+  fn rejects_overrides_incompatible_with_the_preset() {
+    // Hard goes up to 20, so a min of 30 leaves an empty range
+    let config = build(&["--min", "30"]).unwrap();
+    assert!(config.settings(Difficulty::Hard).is_err());
+    assert!(config.settings(Difficulty::Normal).is_ok());
+  }
+
+  #[test]
+  fn rejects_malformed_values() {
+    assert!(build(&["--min", "ten"]).is_err());
+    assert!(build(&["--max"]).is_err());
+    assert!(build(&["--range", "1-1000"]).is_err());
+    assert!(build(&["--verbose"]).is_err());
+  }
+}
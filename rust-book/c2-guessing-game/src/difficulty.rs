@@ -0,0 +1,93 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Difficulty {
+  Easy,
+  Normal,
+  Hard,
+}
+
+// Everything a game needs to know about its rules, once presets and CLI overrides have been combined
+#[derive(Debug, PartialEq)]
+pub struct GameSettings {
+  pub min: u32,
+  pub max: u32,
+  pub max_attempts: Option<u32>,
+  // Whether the player is told "too big" / "too small" after a wrong guess
+  pub hints: bool,
+  // `None` means that there is no time budget
+  pub time_limit: Option<Duration>,
+}
+
+impl GameSettings {
+  pub fn range(&self) -> RangeInclusive<u32> {
+    self.min..=self.max
+  }
+}
+
+impl Difficulty {
+  pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+  pub fn settings(&self) -> GameSettings {
+    match self {
+      Difficulty::Easy => GameSettings { min: 1, max: 50, max_attempts: None, hints: true, time_limit: None },
+      Difficulty::Normal => GameSettings { min: 1, max: 100, max_attempts: Some(10), hints: true, time_limit: None },
+      // Without hints the range has to be small, otherwise winning is just luck
+      Difficulty::Hard => GameSettings { min: 1, max: 20, max_attempts: Some(5), hints: false, time_limit: None },
+    }
+  }
+}
+
+impl fmt::Display for Difficulty {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let name = match self {
+      Difficulty::Easy => "easy",
+      Difficulty::Normal => "normal",
+      Difficulty::Hard => "hard",
+    };
+    write!(f, "{name}")
+  }
+}
+
+impl FromStr for Difficulty {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Difficulty, String> {
+    match s.trim().to_lowercase().as_str() {
+      "easy" => Ok(Difficulty::Easy),
+      "normal" => Ok(Difficulty::Normal),
+      "hard" => Ok(Difficulty::Hard),
+      other => Err(format!("unknown difficulty '{other}' (expected easy, normal or hard)")),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_names_case_insensitively() {
+    assert_eq!("easy".parse(), Ok(Difficulty::Easy));
+    assert_eq!("Normal".parse(), Ok(Difficulty::Normal));
+    assert_eq!(" HARD\n".parse(), Ok(Difficulty::Hard));
+    assert!("impossible".parse::<Difficulty>().is_err());
+  }
+
+  #[test]
+  fn display_round_trips_through_from_str() {
+    for difficulty in Difficulty::ALL {
+      assert_eq!(difficulty.to_string().parse(), Ok(difficulty));
+    }
+  }
+
+  #[test]
+  fn only_hard_hides_hints() {
+    assert!(Difficulty::Easy.settings().hints);
+    assert!(Difficulty::Normal.settings().hints);
+    assert!(!Difficulty::Hard.settings().hints);
+  }
+}
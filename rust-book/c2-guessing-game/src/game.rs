@@ -0,0 +1,400 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::time::{Duration, Instant};
+
+use crate::difficulty::GameSettings;
+
+// What all the game variants have in common, so that the same loop (prompts, timer, session score)
+// can play any of them
+pub trait Game {
+  // Shown to the player after a wrong guess
+  type Feedback: Compact;
+
+  // What a valid guess looks like, e.g. "between 1 and 100"
+  fn describe_guess(&self) -> String;
+
+  // Parses and plays one guess. Invalid input is rejected with a message and doesn't count as an attempt
+  fn play_turn(&mut self, input: &str) -> Result<Turn<Self::Feedback>, String>;
+
+  // Shown when the game is lost, e.g. "The secret number was 42."
+  fn reveal(&self) -> String;
+
+  // Every valid guess, in order
+  fn history(&self) -> Vec<String>;
+
+  fn attempts(&self) -> u32;
+
+  fn limits(&self) -> &Limits;
+
+  // `None` means that the number of attempts is unlimited
+  fn remaining_attempts(&self) -> Option<u32> {
+    self.limits().remaining_attempts(self.attempts())
+  }
+
+  fn elapsed(&self) -> Duration {
+    self.limits().elapsed()
+  }
+
+  // `None` if the game isn't timed
+  fn time_left(&self) -> Option<Duration> {
+    self.limits().time_left()
+  }
+}
+
+// Feedback of a guess, which can be displayed in full or in a short form (to show it next to the guess in a history)
+pub trait Compact: fmt::Display {
+  fn compact(&self) -> String;
+}
+
+pub enum Turn<F> {
+  Wrong(F),
+  Win,
+  // The guess was wrong and there are no attempts left
+  Lose,
+}
+
+// Attempt and time budget of one game
+pub struct Limits {
+  max_attempts: Option<u32>,
+  time_limit: Option<Duration>,
+  started_at: Instant,
+}
+
+impl Limits {
+  pub fn new(max_attempts: Option<u32>, time_limit: Option<Duration>) -> Limits {
+    Limits {
+      max_attempts,
+      time_limit,
+      started_at: Instant::now(),
+    }
+  }
+
+  pub fn remaining_attempts(&self, attempts: u32) -> Option<u32> {
+    self.max_attempts.map(|max| max.saturating_sub(attempts))
+  }
+
+  pub fn elapsed(&self) -> Duration {
+    self.started_at.elapsed()
+  }
+
+  pub fn time_left(&self) -> Option<Duration> {
+    self.time_limit.map(|limit| limit.saturating_sub(self.elapsed()))
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum GuessResult {
+  TooSmall,
+  TooBig,
+  Win,
+  // The guess was wrong and there are no attempts left
+  Lose,
+}
+
+impl fmt::Display for GuessResult {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let description = match self {
+      GuessResult::TooSmall => "too small",
+      GuessResult::TooBig => "too big",
+      GuessResult::Win => "correct",
+      GuessResult::Lose => "wrong, and no attempts left",
+    };
+    write!(f, "{description}")
+  }
+}
+
+// How the last guess compares to the previous one
+#[derive(Debug, PartialEq)]
+pub enum Temperature {
+  Warmer,
+  Colder,
+  Same,
+}
+
+// Keeps the state of one game, independently of where the guesses come from (stdin, tests...)
+pub struct GameState {
+  secret_number: u32,
+  limits: Limits,
+  // Every valid guess, in order
+  history: Vec<u32>,
+}
+
+impl GameState {
+  pub fn new(secret_number: u32, max_attempts: Option<u32>) -> GameState {
+    GameState {
+      secret_number,
+      limits: Limits::new(max_attempts, None),
+      history: Vec::new(),
+    }
+  }
+
+  pub fn with_time_limit(mut self, time_limit: Option<Duration>) -> GameState {
+    self.limits.time_limit = time_limit;
+    self
+  }
+
+  pub fn guess(&mut self, guess: u32) -> GuessResult {
+    self.history.push(guess);
+
+    match guess.cmp(&self.secret_number) {
+      Ordering::Equal => GuessResult::Win,
+      _ if self.remaining_attempts() == Some(0) => GuessResult::Lose,
+      Ordering::Less => GuessResult::TooSmall,
+      Ordering::Greater => GuessResult::TooBig,
+    }
+  }
+
+  pub fn attempts(&self) -> u32 {
+    self.history.len() as u32
+  }
+
+  pub fn history(&self) -> &[u32] {
+    &self.history
+  }
+
+  // Compares the distances of the last two guesses to the secret number. `None` until there are two guesses
+  pub fn temperature(&self) -> Option<Temperature> {
+    let [.., previous, last] = self.history[..] else {
+      return None;
+    };
+    let previous_distance = previous.abs_diff(self.secret_number);
+    let last_distance = last.abs_diff(self.secret_number);
+
+    Some(match last_distance.cmp(&previous_distance) {
+      Ordering::Less => Temperature::Warmer,
+      Ordering::Greater => Temperature::Colder,
+      Ordering::Equal => Temperature::Same,
+    })
+  }
+
+  // `None` means that the number of attempts is unlimited
+  pub fn remaining_attempts(&self) -> Option<u32> {
+    self.limits.remaining_attempts(self.attempts())
+  }
+
+  pub fn secret_number(&self) -> u32 {
+    self.secret_number
+  }
+}
+
+pub struct NumberFeedback {
+  // Whether the guess was smaller (`Less`) or bigger (`Greater`) than the secret number. `None` without hints
+  pub direction: Option<Ordering>,
+  // `None` unless warmer/colder hints are enabled and there was a previous guess
+  pub temperature: Option<Temperature>,
+}
+
+impl fmt::Display for NumberFeedback {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self.direction {
+      Some(Ordering::Less) => write!(f, "Too small!")?,
+      Some(Ordering::Greater) => write!(f, "Too big!")?,
+      _ => write!(f, "Wrong!")?,
+    }
+    match self.temperature {
+      Some(Temperature::Warmer) => write!(f, "\nWarmer..."),
+      Some(Temperature::Colder) => write!(f, "\nColder..."),
+      Some(Temperature::Same) => write!(f, "\nNeither warmer nor colder..."),
+      None => Ok(()),
+    }
+  }
+}
+
+// An arrow pointing to where the secret number is, e.g. "50 ↑" means that the secret number is bigger than 50
+impl Compact for NumberFeedback {
+  fn compact(&self) -> String {
+    let arrow = match self.direction {
+      Some(Ordering::Less) => "↑",
+      Some(Ordering::Greater) => "↓",
+      _ => "✗",
+    };
+    match self.temperature {
+      Some(Temperature::Warmer) => format!("{arrow} warmer"),
+      Some(Temperature::Colder) => format!("{arrow} colder"),
+      Some(Temperature::Same) => format!("{arrow} same"),
+      None => arrow.to_string(),
+    }
+  }
+}
+
+// The number guessing game as played by a person: input validation and hints on top of `GameState`
+pub struct NumberGame {
+  state: GameState,
+  range: RangeInclusive<u32>,
+  hints: bool,
+  warmer_colder: bool,
+}
+
+impl NumberGame {
+  pub fn new(secret_number: u32, settings: &GameSettings, warmer_colder: bool) -> NumberGame {
+    NumberGame {
+      state: GameState::new(secret_number, settings.max_attempts).with_time_limit(settings.time_limit),
+      range: settings.range(),
+      hints: settings.hints,
+      warmer_colder,
+    }
+  }
+}
+
+impl Game for NumberGame {
+  type Feedback = NumberFeedback;
+
+  fn describe_guess(&self) -> String {
+    format!("between {} and {}", self.range.start(), self.range.end())
+  }
+
+  fn play_turn(&mut self, input: &str) -> Result<Turn<NumberFeedback>, String> {
+    let guess: u32 = input
+      .trim()
+      .parse()
+      .map_err(|_| String::from("Your input must be a number"))?;
+    if !self.range.contains(&guess) {
+      return Err(format!("Your guess must be {}", self.describe_guess()));
+    }
+
+    let direction = match self.state.guess(guess) {
+      GuessResult::TooSmall => Ordering::Less,
+      GuessResult::TooBig => Ordering::Greater,
+      GuessResult::Win => return Ok(Turn::Win),
+      GuessResult::Lose => return Ok(Turn::Lose),
+    };
+
+    Ok(Turn::Wrong(NumberFeedback {
+      direction: self.hints.then_some(direction),
+      temperature: if self.warmer_colder { self.state.temperature() } else { None },
+    }))
+  }
+
+  fn reveal(&self) -> String {
+    format!("The secret number was {}.", self.state.secret_number())
+  }
+
+  fn history(&self) -> Vec<String> {
+    self.state.history().iter().map(|guess| guess.to_string()).collect()
+  }
+
+  fn attempts(&self) -> u32 {
+    self.state.attempts()
+  }
+
+  fn limits(&self) -> &Limits {
+    &self.state.limits
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn gives_hints_until_the_number_is_found() {
+    let mut game = GameState::new(42, None);
+
+    assert_eq!(game.guess(10), GuessResult::TooSmall);
+    assert_eq!(game.guess(90), GuessResult::TooBig);
+    assert_eq!(game.guess(42), GuessResult::Win);
+    assert_eq!(game.attempts(), 3);
+    assert_eq!(game.remaining_attempts(), None);
+  }
+
+  #[test]
+  fn keeps_the_history_of_guesses() {
+    let mut game = GameState::new(42, None);
+
+    game.guess(10);
+    game.guess(90);
+    assert_eq!(game.history(), &[10, 90]);
+  }
+
+  #[test]
+  fn compares_the_last_two_guesses() {
+    let mut game = GameState::new(50, None);
+
+    game.guess(10);
+    assert_eq!(game.temperature(), None);
+    game.guess(40);
+    assert_eq!(game.temperature(), Some(Temperature::Warmer));
+    game.guess(99);
+    assert_eq!(game.temperature(), Some(Temperature::Colder));
+    // 1 is as far from 50 as 99 is
+    game.guess(1);
+    assert_eq!(game.temperature(), Some(Temperature::Same));
+  }
+
+  #[test]
+  fn loses_after_max_attempts_wrong_guesses() {
+    let mut game = GameState::new(42, Some(2));
+
+    assert_eq!(game.guess(10), GuessResult::TooSmall);
+    assert_eq!(game.remaining_attempts(), Some(1));
+    assert_eq!(game.guess(90), GuessResult::Lose);
+    assert_eq!(game.remaining_attempts(), Some(0));
+  }
+
+  #[test]
+  fn untimed_games_never_run_out_of_time() {
+    let limits = Limits::new(None, None);
+
+    assert_eq!(limits.time_left(), None);
+  }
+
+  #[test]
+  fn timed_games_run_out_of_time() {
+    let limits = Limits::new(None, Some(Duration::from_secs(3600)));
+    assert!(limits.time_left().unwrap() > Duration::from_secs(3500));
+
+    let limits = Limits::new(None, Some(Duration::ZERO));
+    assert_eq!(limits.time_left(), Some(Duration::ZERO));
+  }
+
+  #[test]
+  fn last_attempt_can_still_win() {
+    let mut game = GameState::new(42, Some(1));
+
+    assert_eq!(game.guess(42), GuessResult::Win);
+  }
+
+  fn number_game(secret_number: u32, hints: bool, warmer_colder: bool) -> NumberGame {
+    let settings = GameSettings { min: 1, max: 100, max_attempts: Some(3), hints, time_limit: None };
+    NumberGame::new(secret_number, &settings, warmer_colder)
+  }
+
+  #[test]
+  fn number_game_rejects_invalid_input_without_counting_it() {
+    let mut game = number_game(42, true, false);
+
+    assert!(game.play_turn("forty-two").is_err());
+    assert!(game.play_turn("101").is_err());
+    assert_eq!(game.attempts(), 0);
+    assert_eq!(game.remaining_attempts(), Some(3));
+  }
+
+  #[test]
+  fn number_game_feedback_follows_the_settings() {
+    let mut game = number_game(42, true, true);
+    let Ok(Turn::Wrong(feedback)) = game.play_turn("10") else { panic!("expected a wrong guess") };
+    assert_eq!(feedback.to_string(), "Too small!");
+    let Ok(Turn::Wrong(feedback)) = game.play_turn(" 50\n") else { panic!("expected a wrong guess") };
+    assert_eq!(feedback.to_string(), "Too big!\nWarmer...");
+    assert_eq!(feedback.compact(), "↓ warmer");
+
+    let mut game = number_game(42, false, false);
+    let Ok(Turn::Wrong(feedback)) = game.play_turn("10") else { panic!("expected a wrong guess") };
+    assert_eq!(feedback.to_string(), "Wrong!");
+    assert_eq!(feedback.compact(), "✗");
+  }
+
+  #[test]
+  fn number_game_ends_with_a_win_or_a_loss() {
+    let mut game = number_game(42, true, false);
+    assert!(matches!(game.play_turn("42"), Ok(Turn::Win)));
+
+    let mut game = number_game(42, true, false);
+    game.play_turn("1").unwrap();
+    game.play_turn("2").unwrap();
+    assert!(matches!(game.play_turn("3"), Ok(Turn::Lose)));
+    assert_eq!(game.reveal(), "The secret number was 42.");
+    assert_eq!(game.history(), vec!["1", "2", "3"]);
+  }
+}
@@ -0,0 +1,49 @@
+use std::io;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+// Lines typed by the player. Reading from stdin blocks and can't be cancelled, so a background
+// thread does the reading and sends each line through a channel. The receiving end can then wait
+// with a timeout, which is what the timed mode needs.
+pub struct Input {
+  lines: Receiver<String>,
+}
+
+impl Input {
+  pub fn from_stdin() -> Input {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || loop {
+      let mut line = String::new();
+
+      // https://doc.rust-lang.org/std/io/struct.Stdin.html
+      let bytes_read = io::stdin()
+          .read_line(&mut line)
+          .expect("Failed to read line");
+
+      // Stop on end of input, or when nobody is listening anymore. Dropping the sender closes the channel
+      if bytes_read == 0 || sender.send(line).is_err() {
+        break;
+      }
+    });
+
+    Input { lines: receiver }
+  }
+
+  // Waits for the next line. Returns `None` when there is no more input (e.g. Ctrl+D or end of a pipe)
+  pub fn read_line(&self) -> Option<String> {
+    self.lines.recv().ok()
+  }
+
+  // Like `read_line`, but gives up after `timeout`
+  pub fn read_line_timeout(&self, timeout: Duration) -> Result<String, RecvTimeoutError> {
+    self.lines.recv_timeout(timeout)
+  }
+
+  // Throws away the lines that were typed but not read yet, and returns how many there were.
+  // E.g. a guess submitted just after the time ran out is not an answer to the next question
+  pub fn discard_pending(&self) -> usize {
+    self.lines.try_iter().count()
+  }
+}
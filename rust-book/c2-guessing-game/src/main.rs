@@ -1,38 +1,305 @@
-use std::io;
-use std::cmp::Ordering;
+use std::env;
+use std::io::{self, IsTerminal};
+use std::process;
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::Duration;
 use rand::Rng;
 
+mod config;
+mod difficulty;
+mod game;
+mod input;
+mod network;
+mod protocol;
+mod session;
+mod stats;
+mod strategy;
+mod ui;
+mod word_game;
+use config::{Config, Mode, Variant};
+use difficulty::{Difficulty, GameSettings};
+use game::{Game, GameState, GuessResult, NumberGame, Turn};
+use input::Input;
+use session::{RoundOutcome, Session};
+use stats::{GameRecord, Stats};
+use ui::{PlainUi, TerminalUi, Ui, View};
+use word_game::WordGame;
+
+// Exit code used when the player quits after losing the last game (1 is used for invalid arguments)
+const LOSE_EXIT_CODE: i32 = 2;
+// How often the timer is updated while waiting for a guess
+const TICK: Duration = Duration::from_secs(1);
+
 fn main() {
+  let config = Config::build(env::args()).unwrap_or_else(|err| {
+    eprintln!("Problem parsing arguments: {err}");
+    process::exit(1);
+  });
+
+  if config.mode == Mode::Stats {
+    print_stats(&config);
+    return;
+  }
+
   println!("** Welcome to the number guessing game! **\n");
-  let secret_number = rand::thread_rng().gen_range(1..=100);
-  println!("The secret number is: {secret_number}");
+  let input = Input::from_stdin();
+
+  // The player who joins gets the rules from the host
+  if let Mode::Join(address) = &config.mode {
+    network::join(address, &input).unwrap_or_else(|err| exit_with_network_error(err));
+    return;
+  }
+
+  let difficulty = config.difficulty.unwrap_or_else(|| choose_difficulty(&input));
+  let settings = config.settings(difficulty).unwrap_or_else(|err| {
+    eprintln!("Problem with the game settings: {err}");
+    process::exit(1);
+  });
+
+  if let Mode::Host(address) = &config.mode {
+    network::host(address, &settings, &input).unwrap_or_else(|err| exit_with_network_error(err));
+    return;
+  }
+
+  if config.mode == Mode::Auto {
+    play_auto(&settings);
+    return;
+  }
+
+  let mut session = Session::new(config, difficulty, settings);
 
   loop {
-    println!("Please input your guess.");
-    let mut guess = String::new(); // mutable variable (vars are immutable by default)
-  
-    // https://doc.rust-lang.org/std/io/struct.Stdin.html
-    io::stdin()
-        .read_line(&mut guess)
-        .expect("Failed to read line");
-  
-    // variable shadowing => same name, used to change type but keeping name
-    let guess: u32 = match guess.trim().parse() {
-      Ok(foo) => foo,
-      Err(_) => {
-        println!("Your input must be a number. Try again...");
+    if play_round(&mut session, &input).is_none() {
+      println!("No more input.");
+      break;
+    }
+    if !ask_play_again(&input) {
+      break;
+    }
+  }
+
+  println!("\n** Session summary **\n{}", session.summary());
+  if session.last_round().is_some_and(|round| !round.won) {
+    process::exit(LOSE_EXIT_CODE);
+  }
+}
+
+// Plays one game of the chosen variant and records its outcome in the session.
+// Returns `None` if the input ended mid-game
+fn play_round(session: &mut Session, input: &Input) -> Option<RoundOutcome> {
+  let config = &session.config;
+  let settings = &session.settings;
+
+  let title = match config.variant {
+    Variant::Numbers => "Number guessing game",
+    Variant::Words => "Word guessing game",
+  };
+  // The terminal UI redraws the screen, which only makes sense when a person is looking at it
+  let mut ui: Box<dyn Ui> = if config.plain || !io::stdout().is_terminal() {
+    Box::new(PlainUi)
+  } else {
+    Box::new(TerminalUi::new(title))
+  };
+
+  let outcome = match config.variant {
+    Variant::Numbers => {
+      let secret_number = rand::thread_rng().gen_range(settings.range());
+      if config.debug {
+        ui.note(&format!("The secret number is: {secret_number}"));
+      }
+      let mut game = NumberGame::new(secret_number, settings, config.warmer_colder);
+      let outcome = play(&mut game, input, ui.as_mut())?;
+      if config.mode == Mode::Race {
+        print_race_result(&outcome, secret_number, settings);
+      }
+      outcome
+    }
+    Variant::Words => {
+      let word = word_game::random_word();
+      if config.debug {
+        ui.note(&format!("The secret word is: {word}"));
+      }
+      play(&mut WordGame::new(word, settings), input, ui.as_mut())?
+    }
+  };
+
+  session.record(outcome);
+  let record = GameRecord {
+    variant: session.config.variant,
+    difficulty: session.difficulty,
+    custom: session.config.has_overrides(),
+    won: outcome.won,
+    attempts: outcome.attempts,
+    duration: outcome.duration,
+  };
+  // Losing the statistics isn't a reason to stop playing
+  if let Err(err) = stats::append(session.config.stats_file(), &record) {
+    eprintln!("Could not record the game in {}: {err}", session.config.stats_file());
+  }
+  Some(outcome)
+}
+
+// Asks for guesses until the game is over. Works with any variant thanks to the `Game` trait,
+// and with any UI thanks to the `Ui` trait: this function only decides what happens, not how it looks
+fn play(game: &mut impl Game, input: &Input, ui: &mut dyn Ui) -> Option<RoundOutcome> {
+  if let Some(time_left) = game.time_left() {
+    ui.note(&format!("You have {:.0} seconds. The clock is ticking!", time_left.as_secs_f64()));
+  }
+
+  let won = loop {
+    ui.prompt(&View::of(game));
+
+    // In timed mode, stop waiting for the player as soon as the time is over
+    let guess = match game.time_left() {
+      None => input.read_line()?,
+      Some(_) => match read_line_with_timer(game, input, ui) {
+        Ok(line) => line,
+        Err(RecvTimeoutError::Timeout) => {
+          // Lines submitted just as the time ran out came too late, and must not answer the next question
+          let ignored = if input.discard_pending() > 0 { " (late input was ignored)" } else { "" };
+          ui.game_over(&View::of(game), false, &format!("Time's up! You lose! {}{ignored}", game.reveal()));
+          break false;
+        }
+        Err(RecvTimeoutError::Disconnected) => return None,
+      },
+    };
+
+    let turn = match game.play_turn(&guess) {
+      Ok(turn) => turn,
+      Err(err) => {
+        ui.invalid_input(&View::of(game), &err);
         continue;
       }
     };
-  
-    match guess.cmp(&secret_number) {
-      Ordering::Less => println!("Too small!"),
-      Ordering::Greater => println!("Too big!"),
-      Ordering::Equal => {
-        println!("You win!");
-        break;
+
+    match turn {
+      Turn::Wrong(feedback) => ui.wrong_guess(&View::of(game), &feedback),
+      Turn::Win => {
+        ui.game_over(&View::of(game), true, &format!("You win! (attempts: {})", game.attempts()));
+        break true;
+      }
+      Turn::Lose => {
+        ui.game_over(&View::of(game), false, &format!("No attempts left. You lose! {}", game.reveal()));
+        break false;
       }
     }
+  };
+
+  Some(RoundOutcome { won, attempts: game.attempts(), duration: game.elapsed() })
+}
+
+// Waits for a line at most until the time is over, letting the UI update the timer every second
+fn read_line_with_timer(game: &impl Game, input: &Input, ui: &mut dyn Ui) -> Result<String, RecvTimeoutError> {
+  loop {
+    let time_left = game.time_left().unwrap_or_default();
+    if time_left.is_zero() {
+      return Err(RecvTimeoutError::Timeout);
+    }
+    match input.read_line_timeout(time_left.min(TICK)) {
+      Err(RecvTimeoutError::Timeout) => ui.tick(&View::of(game)),
+      result => return result,
+    }
+  }
+}
+
+// The computer plays alone, printing every step
+fn play_auto(settings: &GameSettings) {
+  let secret_number = rand::thread_rng().gen_range(settings.range());
+  let mut strategy = strategy::for_settings(settings);
+  let mut game = GameState::new(secret_number, settings.max_attempts);
+  println!("The secret number is: {secret_number}. Solving it with {}...", strategy.name());
+
+  for (guess, result) in strategy::play(strategy.as_mut(), &mut game) {
+    println!("  {guess}: {result}");
+  }
+  println!("Total attempts: {}", game.attempts());
+}
+
+// Lets the solver guess the same number and compares its attempts with the ones of the player
+fn print_race_result(player: &RoundOutcome, secret_number: u32, settings: &GameSettings) {
+  let mut strategy = strategy::for_settings(settings);
+  let mut game = GameState::new(secret_number, settings.max_attempts);
+  let solver_won = strategy::play(strategy.as_mut(), &mut game)
+    .last()
+    .is_some_and(|(_, result)| *result == GuessResult::Win);
+  let solver_attempts = game.attempts();
+
+  if solver_won {
+    println!("The solver ({}) found it in {} attempts.", strategy.name(), solver_attempts);
+  } else {
+    println!("The solver ({}) ran out of attempts.", strategy.name());
+  }
+
+  match (player.won, solver_won) {
+    (true, true) if player.attempts < solver_attempts => println!("You beat the solver!"),
+    (true, true) if player.attempts == solver_attempts => println!("It's a tie!"),
+    (true, false) => println!("You beat the solver!"),
+    (false, false) => println!("Nobody wins the race."),
+    _ => println!("The solver wins the race!"),
+  }
+}
+
+fn print_stats(config: &Config) {
+  let records = stats::load(config.stats_file()).unwrap_or_else(|err| {
+    eprintln!("Problem reading {}: {err}", config.stats_file());
+    process::exit(1);
+  });
+  println!("** Statistics of {} games **\n{}", records.len(), Stats::new(&records));
+}
+
+fn exit_with_network_error(err: io::Error) -> ! {
+  eprintln!("Network error: {err}");
+  process::exit(1);
+}
+
+// Only an explicit answer counts, so that a guess typed too late (e.g. after the time ran out)
+// doesn't end the session
+fn ask_play_again(input: &Input) -> bool {
+  println!("\nPlay again? (y/n)");
+  loop {
+    let Some(answer) = input.read_line() else {
+      return false;
+    };
+    match answer.trim().to_lowercase().as_str() {
+      "y" | "yes" => return true,
+      "n" | "no" => return false,
+      _ => println!("Please answer y or n."),
+    }
+  }
+}
+
+// Asks for a difficulty until a valid one is given. An empty line (or no input at all) picks "normal"
+fn choose_difficulty(input: &Input) -> Difficulty {
+  println!("Choose a difficulty:");
+  for (i, difficulty) in Difficulty::ALL.iter().enumerate() {
+    let settings = difficulty.settings();
+    let attempts = match settings.max_attempts {
+      Some(max) => format!("{max} attempts"),
+      None => String::from("unlimited attempts"),
+    };
+    let hints = if settings.hints { "with hints" } else { "no hints" };
+    println!("  {}) {difficulty}: {} to {}, {attempts}, {hints}", i + 1, settings.min, settings.max);
+  }
+
+  loop {
+    println!("Your choice [normal]:");
+    let Some(choice) = input.read_line() else {
+      return Difficulty::Normal;
+    };
+    let choice = choice.trim();
+    if choice.is_empty() {
+      return Difficulty::Normal;
+    }
+
+    // Both the number in the menu and the name are accepted
+    let by_number = choice
+      .parse::<usize>()
+      .ok()
+      .and_then(|n| n.checked_sub(1))
+      .and_then(|i| Difficulty::ALL.get(i).copied());
+    match by_number.map_or_else(|| choice.parse(), Ok) {
+      Ok(difficulty) => return difficulty,
+      Err(err) => println!("{err}. Try again..."),
+    }
   }
-  
 }
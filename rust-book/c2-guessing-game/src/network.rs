@@ -0,0 +1,144 @@
+use std::io::{self, BufReader};
+use std::net::{TcpListener, TcpStream};
+
+use crate::difficulty::GameSettings;
+use crate::game::{GameState, GuessResult};
+use crate::input::Input;
+use crate::protocol::{self, Message};
+
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+
+// Waits for one player to join, lets the local player pick the secret number and answers the guesses
+// of the other player until the game is over
+pub fn host(address: &str, settings: &GameSettings, input: &Input) -> io::Result<()> {
+  let listener = TcpListener::bind(address)?;
+  println!("Waiting for a player to join on {}...", listener.local_addr()?);
+  let (stream, peer) = listener.accept()?;
+  println!("Player connected from {peer}.");
+
+  // Reading and writing need two handles to the same socket: the reader is buffered to read whole lines
+  let mut reader = BufReader::new(stream.try_clone()?);
+  let mut writer = stream;
+
+  let secret_number = pick_secret_number(settings, input)?;
+  let mut game = GameState::new(secret_number, settings.max_attempts);
+  protocol::send(&mut writer, &Message::Start {
+    min: settings.min,
+    max: settings.max,
+    max_attempts: settings.max_attempts,
+  })?;
+  println!("Waiting for guesses...");
+
+  loop {
+    let guess = match protocol::receive(&mut reader)? {
+      Some(Message::Guess(guess)) => guess,
+      Some(other) => return Err(unexpected(other)),
+      None => {
+        println!("The other player left the game.");
+        return Ok(());
+      }
+    };
+
+    if !settings.range().contains(&guess) {
+      let reason = format!("guess must be between {} and {}", settings.min, settings.max);
+      protocol::send(&mut writer, &Message::Invalid(reason))?;
+      continue;
+    }
+
+    let reply = match game.guess(guess) {
+      GuessResult::TooSmall if settings.hints => Message::TooSmall,
+      GuessResult::TooBig if settings.hints => Message::TooBig,
+      GuessResult::TooSmall | GuessResult::TooBig => Message::Wrong,
+      GuessResult::Win => Message::Win { attempts: game.attempts() },
+      GuessResult::Lose => Message::Lose { secret_number },
+    };
+    protocol::send(&mut writer, &reply)?;
+
+    match reply {
+      Message::Win { attempts } => {
+        println!("The other player guessed {guess}. They win! (attempts: {attempts})");
+        return Ok(());
+      }
+      Message::Lose { .. } => {
+        println!("The other player guessed {guess} and has no attempts left. You win!");
+        return Ok(());
+      }
+      _ => println!("The other player guessed {guess}."),
+    }
+  }
+}
+
+// Connects to a host and sends the guesses of the local player
+pub fn join(address: &str, input: &Input) -> io::Result<()> {
+  let stream = TcpStream::connect(address)?;
+  println!("Connected to {address}. Waiting for the host to pick a number...");
+  let mut reader = BufReader::new(stream.try_clone()?);
+  let mut writer = stream;
+
+  let (min, max, mut remaining_attempts) = match protocol::receive(&mut reader)? {
+    Some(Message::Start { min, max, max_attempts }) => (min, max, max_attempts),
+    Some(other) => return Err(unexpected(other)),
+    None => return Err(host_left()),
+  };
+
+  loop {
+    match remaining_attempts {
+      Some(remaining) => println!("Please input your guess (between {min} and {max}, {remaining} attempts left)."),
+      None => println!("Please input your guess (between {min} and {max})."),
+    }
+    let Some(guess) = input.read_line() else {
+      println!("No more input. Leaving the game.");
+      return Ok(());
+    };
+    let guess: u32 = match guess.trim().parse() {
+      Ok(number) => number,
+      Err(_) => {
+        println!("Your input must be a number. Try again...");
+        continue;
+      }
+    };
+
+    protocol::send(&mut writer, &Message::Guess(guess))?;
+    match protocol::receive(&mut reader)? {
+      Some(Message::TooSmall) => println!("Too small!"),
+      Some(Message::TooBig) => println!("Too big!"),
+      Some(Message::Wrong) => println!("Wrong!"),
+      Some(Message::Invalid(reason)) => {
+        println!("The host rejected your guess: {reason}. Try again...");
+        continue;
+      }
+      Some(Message::Win { attempts }) => {
+        println!("You win! (attempts: {attempts})");
+        return Ok(());
+      }
+      Some(Message::Lose { secret_number }) => {
+        println!("No attempts left. You lose! The secret number was {secret_number}.");
+        return Ok(());
+      }
+      Some(other) => return Err(unexpected(other)),
+      None => return Err(host_left()),
+    }
+    remaining_attempts = remaining_attempts.map(|remaining| remaining.saturating_sub(1));
+  }
+}
+
+fn pick_secret_number(settings: &GameSettings, input: &Input) -> io::Result<u32> {
+  loop {
+    println!("Pick the secret number (between {} and {}):", settings.min, settings.max);
+    let Some(line) = input.read_line() else {
+      return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "no more input"));
+    };
+    match line.trim().parse() {
+      Ok(number) if settings.range().contains(&number) => return Ok(number),
+      _ => println!("The secret number must be a number between {} and {}. Try again...", settings.min, settings.max),
+    }
+  }
+}
+
+fn unexpected(message: Message) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, format!("unexpected message '{message}'"))
+}
+
+fn host_left() -> io::Error {
+  io::Error::new(io::ErrorKind::ConnectionAborted, "the host left the game")
+}
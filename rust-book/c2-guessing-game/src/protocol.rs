@@ -0,0 +1,169 @@
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+// Messages exchanged between the host (who picks the secret number) and the guesser.
+// Each message travels as one line of text: a keyword in capitals followed by its arguments, e.g. "GUESS 42"
+#[derive(Debug, PartialEq)]
+pub enum Message {
+  // Host -> guesser, once the secret number is picked. `max_attempts` is sent as "-" when unlimited
+  Start { min: u32, max: u32, max_attempts: Option<u32> },
+  // Guesser -> host
+  Guess(u32),
+  // Host -> guesser, answers to a guess
+  TooSmall,
+  TooBig,
+  // Wrong guess, when the host plays without hints
+  Wrong,
+  Win { attempts: u32 },
+  Lose { secret_number: u32 },
+  // The guess was rejected (e.g. out of range) and didn't count as an attempt
+  Invalid(String),
+}
+
+impl fmt::Display for Message {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Message::Start { min, max, max_attempts: Some(attempts) } => write!(f, "START {min} {max} {attempts}"),
+      Message::Start { min, max, max_attempts: None } => write!(f, "START {min} {max} -"),
+      Message::Guess(guess) => write!(f, "GUESS {guess}"),
+      Message::TooSmall => write!(f, "TOO_SMALL"),
+      Message::TooBig => write!(f, "TOO_BIG"),
+      Message::Wrong => write!(f, "WRONG"),
+      Message::Win { attempts } => write!(f, "WIN {attempts}"),
+      Message::Lose { secret_number } => write!(f, "LOSE {secret_number}"),
+      Message::Invalid(reason) => write!(f, "INVALID {reason}"),
+    }
+  }
+}
+
+impl FromStr for Message {
+  type Err = String;
+
+  fn from_str(line: &str) -> Result<Message, String> {
+    let line = line.trim_end_matches(['\r', '\n']);
+    let (keyword, args) = line.split_once(' ').unwrap_or((line, ""));
+    let numbers: Vec<&str> = args.split_whitespace().collect();
+
+    let message = match (keyword, numbers.as_slice()) {
+      ("START", [min, max, "-"]) => Message::Start {
+        min: parse_number(min)?,
+        max: parse_number(max)?,
+        max_attempts: None,
+      },
+      ("START", [min, max, attempts]) => Message::Start {
+        min: parse_number(min)?,
+        max: parse_number(max)?,
+        max_attempts: Some(parse_attempts(attempts)?),
+      },
+      ("GUESS", [guess]) => Message::Guess(parse_number(guess)?),
+      ("TOO_SMALL", []) => Message::TooSmall,
+      ("TOO_BIG", []) => Message::TooBig,
+      ("WRONG", []) => Message::Wrong,
+      ("WIN", [attempts]) => Message::Win { attempts: parse_number(attempts)? },
+      ("LOSE", [secret_number]) => Message::Lose { secret_number: parse_number(secret_number)? },
+      // The reason is free text, so it is not split into words
+      ("INVALID", _) => Message::Invalid(args.to_string()),
+      _ => return Err(format!("malformed message '{line}'")),
+    };
+
+    Ok(message)
+  }
+}
+
+fn parse_number(value: &str) -> Result<u32, String> {
+  value.parse().map_err(|_| format!("expected a number, but got '{value}'"))
+}
+
+// Like `--max-attempts`, 0 is rejected: a game without any attempt can't be played
+fn parse_attempts(value: &str) -> Result<u32, String> {
+  match parse_number(value)? {
+    0 => Err(String::from("max attempts must be at least 1")),
+    attempts => Ok(attempts),
+  }
+}
+
+pub fn send(writer: &mut impl Write, message: &Message) -> io::Result<()> {
+  writeln!(writer, "{message}")?;
+  writer.flush()
+}
+
+// Returns `None` when the other side closed the connection
+pub fn receive(reader: &mut impl BufRead) -> io::Result<Option<Message>> {
+  let mut line = String::new();
+  if reader.read_line(&mut line)? == 0 {
+    return Ok(None);
+  }
+
+  line
+    .parse()
+    .map(Some)
+    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::io::Cursor;
+
+  fn all_messages() -> Vec<Message> {
+    vec![
+      Message::Start { min: 1, max: 100, max_attempts: Some(10) },
+      Message::Start { min: 5, max: 50, max_attempts: None },
+      Message::Guess(42),
+      Message::TooSmall,
+      Message::TooBig,
+      Message::Wrong,
+      Message::Win { attempts: 3 },
+      Message::Lose { secret_number: 7 },
+      Message::Invalid(String::from("guess must be between 1 and 100")),
+    ]
+  }
+
+  #[test]
+  fn messages_round_trip_through_text() {
+    for message in all_messages() {
+      assert_eq!(message.to_string().parse(), Ok(message));
+    }
+  }
+
+  #[test]
+  fn serializes_to_keywords_and_arguments() {
+    assert_eq!(Message::Guess(42).to_string(), "GUESS 42");
+    assert_eq!(Message::Start { min: 1, max: 20, max_attempts: None }.to_string(), "START 1 20 -");
+  }
+
+  #[test]
+  fn rejects_malformed_lines() {
+    assert!("".parse::<Message>().is_err());
+    assert!("HELLO".parse::<Message>().is_err());
+    assert!("GUESS".parse::<Message>().is_err());
+    assert!("GUESS forty-two".parse::<Message>().is_err());
+    assert!("GUESS 1 2".parse::<Message>().is_err());
+    assert!("START 1 100".parse::<Message>().is_err());
+    assert!("START 1 100 0".parse::<Message>().is_err());
+    assert!("TOO_BIG 3".parse::<Message>().is_err());
+  }
+
+  #[test]
+  fn sends_and_receives_one_message_per_line() {
+    let mut buffer = Vec::new();
+    for message in all_messages() {
+      send(&mut buffer, &message).unwrap();
+    }
+
+    let mut reader = Cursor::new(buffer);
+    for message in all_messages() {
+      assert_eq!(receive(&mut reader).unwrap(), Some(message));
+    }
+    assert_eq!(receive(&mut reader).unwrap(), None);
+  }
+
+  #[test]
+  fn receiving_garbage_is_an_invalid_data_error() {
+    let mut reader = Cursor::new("NOT A MESSAGE\n");
+    let err = receive(&mut reader).unwrap_err();
+
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+  }
+}
@@ -0,0 +1,108 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::difficulty::{Difficulty, GameSettings};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoundOutcome {
+  pub won: bool,
+  pub attempts: u32,
+  pub duration: Duration,
+}
+
+// Options and settings shared by all the rounds played since the program started, and their results
+pub struct Session {
+  pub config: Config,
+  // The preset the settings are based on, kept to record the games in the statistics
+  pub difficulty: Difficulty,
+  pub settings: GameSettings,
+  rounds: Vec<RoundOutcome>,
+}
+
+pub struct SessionSummary {
+  pub games_played: usize,
+  pub wins: usize,
+  // `None` if no game was played
+  pub average_attempts: Option<f64>,
+}
+
+impl Session {
+  pub fn new(config: Config, difficulty: Difficulty, settings: GameSettings) -> Session {
+    Session {
+      config,
+      difficulty,
+      settings,
+      rounds: Vec::new(),
+    }
+  }
+
+  pub fn record(&mut self, outcome: RoundOutcome) {
+    self.rounds.push(outcome);
+  }
+
+  pub fn last_round(&self) -> Option<&RoundOutcome> {
+    self.rounds.last()
+  }
+
+  pub fn summary(&self) -> SessionSummary {
+    let games_played = self.rounds.len();
+    let total_attempts: u32 = self.rounds.iter().map(|round| round.attempts).sum();
+
+    SessionSummary {
+      games_played,
+      wins: self.rounds.iter().filter(|round| round.won).count(),
+      average_attempts: if games_played == 0 {
+        None
+      } else {
+        Some(total_attempts as f64 / games_played as f64)
+      },
+    }
+  }
+}
+
+impl fmt::Display for SessionSummary {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    writeln!(f, "Games played: {}", self.games_played)?;
+    writeln!(f, "Wins: {}", self.wins)?;
+    match self.average_attempts {
+      Some(average) => write!(f, "Average attempts: {average:.1}"),
+      None => write!(f, "Average attempts: -"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn round(won: bool, attempts: u32) -> RoundOutcome {
+    RoundOutcome { won, attempts, duration: Duration::from_secs(10) }
+  }
+
+  #[test]
+  fn summarizes_an_empty_session() {
+    let session = Session::new(Config::default(), Difficulty::Normal, Difficulty::Normal.settings());
+    let summary = session.summary();
+
+    assert_eq!(summary.games_played, 0);
+    assert_eq!(summary.wins, 0);
+    assert_eq!(summary.average_attempts, None);
+    assert!(session.last_round().is_none());
+  }
+
+  #[test]
+  fn summarizes_recorded_rounds() {
+    let mut session = Session::new(Config::default(), Difficulty::Normal, Difficulty::Normal.settings());
+    session.record(round(true, 4));
+    session.record(round(false, 10));
+    session.record(round(true, 7));
+    let summary = session.summary();
+
+    assert_eq!(summary.games_played, 3);
+    assert_eq!(summary.wins, 2);
+    assert_eq!(summary.average_attempts, Some(7.0));
+    assert_eq!(session.last_round(), Some(&round(true, 7)));
+    assert_eq!(summary.to_string(), "Games played: 3\nWins: 2\nAverage attempts: 7.0");
+  }
+}
@@ -0,0 +1,288 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::config::Variant;
+use crate::difficulty::Difficulty;
+
+// Relative to the directory where the game is started, unless `--stats-file` is given
+pub const DEFAULT_STATS_FILE: &str = "guessing-game-stats.csv";
+const HEADER: &str = "variant,difficulty,settings,result,attempts,duration_ms";
+
+// One finished game, as stored in the log
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameRecord {
+  pub variant: Variant,
+  pub difficulty: Difficulty,
+  // Played with options that override the preset of the difficulty (e.g. `--range` or `--max-attempts`)
+  pub custom: bool,
+  pub won: bool,
+  pub attempts: u32,
+  pub duration: Duration,
+}
+
+// A line of the CSV file, e.g. "numbers,normal,preset,win,4,12345"
+impl fmt::Display for GameRecord {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let settings = if self.custom { "custom" } else { "preset" };
+    let result = if self.won { "win" } else { "lose" };
+    write!(
+      f,
+      "{},{},{settings},{result},{},{}",
+      self.variant,
+      self.difficulty,
+      self.attempts,
+      self.duration.as_millis()
+    )
+  }
+}
+
+impl FromStr for GameRecord {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<GameRecord, String> {
+    let fields: Vec<&str> = s.trim().split(',').collect();
+    let [variant, difficulty, settings, result, attempts, duration] = fields[..] else {
+      return Err(format!("expected 6 fields, but got {}", fields.len()));
+    };
+
+    Ok(GameRecord {
+      variant: variant.parse()?,
+      difficulty: difficulty.parse()?,
+      custom: match settings {
+        "preset" => false,
+        "custom" => true,
+        other => return Err(format!("unknown settings '{other}'")),
+      },
+      won: match result {
+        "win" => true,
+        "lose" => false,
+        other => return Err(format!("unknown result '{other}'")),
+      },
+      attempts: attempts.parse().map_err(|_| format!("invalid attempts '{attempts}'"))?,
+      duration: Duration::from_millis(duration.parse().map_err(|_| format!("invalid duration '{duration}'"))?),
+    })
+  }
+}
+
+// Adds a game at the end of the log, creating the file (with a header) if it doesn't exist yet.
+// Games are never rewritten, so an interrupted write can at most damage the last line
+pub fn append(path: &str, record: &GameRecord) -> io::Result<()> {
+  let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+  if file.metadata()?.len() == 0 {
+    writeln!(file, "{HEADER}")?;
+  }
+  writeln!(file, "{record}")
+}
+
+// Reads all the games of the log. A missing file just means that no game was played yet
+pub fn load(path: &str) -> io::Result<Vec<GameRecord>> {
+  let contents = match fs::read_to_string(path) {
+    Ok(contents) => contents,
+    Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+    Err(err) => return Err(err),
+  };
+
+  let mut lines = contents.lines().enumerate().peekable();
+  if lines.next_if(|(_, line)| *line == HEADER).is_none() && lines.peek().is_some() {
+    let message = format!("unknown format: the first line should be '{HEADER}'");
+    return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+  }
+
+  lines
+    .filter(|(_, line)| !line.trim().is_empty())
+    .map(|(i, line)| {
+      line
+        .parse()
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {err}", i + 1)))
+    })
+    .collect()
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct DifficultyStats {
+  pub games: usize,
+  pub wins: usize,
+  pub total_duration: Duration,
+  // Number of games won with each number of attempts. A `BTreeMap` keeps them sorted
+  pub attempts_to_win: BTreeMap<u32, usize>,
+}
+
+impl DifficultyStats {
+  pub fn win_rate(&self) -> f64 {
+    self.wins as f64 / self.games as f64
+  }
+
+  pub fn average_duration(&self) -> Duration {
+    self.total_duration / self.games as u32
+  }
+}
+
+// Aggregates of the recorded games, per variant and difficulty. Combinations without games are left out.
+// Games with custom settings are only counted: e.g. a "hard" game with `--range 1..10` says nothing about "hard"
+pub struct Stats {
+  pub per_difficulty: Vec<(Variant, Difficulty, DifficultyStats)>,
+  pub custom_games: usize,
+}
+
+impl Stats {
+  pub fn new(records: &[GameRecord]) -> Stats {
+    let per_difficulty = Variant::ALL
+      .into_iter()
+      .flat_map(|variant| Difficulty::ALL.map(|difficulty| (variant, difficulty)))
+      .map(|(variant, difficulty)| {
+        let mut stats = DifficultyStats::default();
+        let games = records
+          .iter()
+          .filter(|record| !record.custom && record.variant == variant && record.difficulty == difficulty);
+        for record in games {
+          stats.games += 1;
+          stats.total_duration += record.duration;
+          if record.won {
+            stats.wins += 1;
+            *stats.attempts_to_win.entry(record.attempts).or_insert(0) += 1;
+          }
+        }
+        (variant, difficulty, stats)
+      })
+      .filter(|(_, _, stats)| stats.games > 0)
+      .collect();
+    let custom_games = records.iter().filter(|record| record.custom).count();
+
+    Stats { per_difficulty, custom_games }
+  }
+}
+
+impl fmt::Display for Stats {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    if self.per_difficulty.is_empty() && self.custom_games == 0 {
+      return write!(f, "No games recorded yet.");
+    }
+
+    let mut lines = Vec::new();
+    for (variant, difficulty, stats) in &self.per_difficulty {
+      lines.push(format!(
+        "{variant}, {difficulty}: {} games, {} wins ({:.0}%), {:.1}s per game on average",
+        stats.games,
+        stats.wins,
+        stats.win_rate() * 100.0,
+        stats.average_duration().as_secs_f64()
+      ));
+      // A small histogram: one `#` per game won with that number of attempts
+      for (attempts, count) in &stats.attempts_to_win {
+        lines.push(format!("  {attempts:>3} attempts | {} {count}", "#".repeat(*count)));
+      }
+    }
+    if self.custom_games > 0 {
+      lines.push(format!("{} games with custom settings (not included above)", self.custom_games));
+    }
+    write!(f, "{}", lines.join("\n"))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn record(difficulty: Difficulty, won: bool, attempts: u32, seconds: u64) -> GameRecord {
+    GameRecord {
+      variant: Variant::Numbers,
+      difficulty,
+      custom: false,
+      won,
+      attempts,
+      duration: Duration::from_secs(seconds),
+    }
+  }
+
+  #[test]
+  fn records_round_trip_through_csv_lines() {
+    let game = GameRecord {
+      variant: Variant::Numbers,
+      difficulty: Difficulty::Normal,
+      custom: false,
+      won: true,
+      attempts: 4,
+      duration: Duration::from_millis(12345),
+    };
+    assert_eq!(game.to_string(), "numbers,normal,preset,win,4,12345");
+    assert_eq!(game.to_string().parse(), Ok(game));
+
+    let game = GameRecord { variant: Variant::Words, custom: true, ..game };
+    assert_eq!(game.to_string(), "words,normal,custom,win,4,12345");
+    assert_eq!(game.to_string().parse(), Ok(game));
+  }
+
+  #[test]
+  fn rejects_malformed_lines() {
+    assert!("normal,win,4,100".parse::<GameRecord>().is_err());
+    assert!("numbers,normal,preset,win,4".parse::<GameRecord>().is_err());
+    assert!("letters,normal,preset,win,4,100".parse::<GameRecord>().is_err());
+    assert!("numbers,extreme,preset,win,4,100".parse::<GameRecord>().is_err());
+    assert!("numbers,normal,other,win,4,100".parse::<GameRecord>().is_err());
+    assert!("numbers,normal,preset,draw,4,100".parse::<GameRecord>().is_err());
+    assert!("numbers,normal,preset,win,four,100".parse::<GameRecord>().is_err());
+  }
+
+  #[test]
+  fn aggregates_per_variant_and_difficulty() {
+    let records = [
+      record(Difficulty::Normal, true, 5, 10),
+      record(Difficulty::Normal, false, 10, 30),
+      GameRecord { variant: Variant::Words, ..record(Difficulty::Normal, true, 2, 50) },
+      record(Difficulty::Normal, true, 5, 20),
+      record(Difficulty::Hard, true, 3, 8),
+      GameRecord { custom: true, ..record(Difficulty::Hard, true, 1, 1) },
+    ];
+    let stats = Stats::new(&records);
+
+    let groups: Vec<(Variant, Difficulty)> =
+      stats.per_difficulty.iter().map(|(variant, difficulty, _)| (*variant, *difficulty)).collect();
+    assert_eq!(
+      groups,
+      vec![(Variant::Numbers, Difficulty::Normal), (Variant::Numbers, Difficulty::Hard), (Variant::Words, Difficulty::Normal)]
+    );
+    assert_eq!(stats.custom_games, 1);
+
+    let normal = &stats.per_difficulty[0].2;
+    assert_eq!(normal.games, 3);
+    assert_eq!(normal.wins, 2);
+    assert_eq!(normal.average_duration(), Duration::from_secs(20));
+    assert_eq!(normal.attempts_to_win, BTreeMap::from([(5, 2)]));
+    assert_eq!(
+      stats.to_string(),
+      "numbers, normal: 3 games, 2 wins (67%), 20.0s per game on average\n    5 attempts | ## 2\n\
+       numbers, hard: 1 games, 1 wins (100%), 8.0s per game on average\n    3 attempts | # 1\n\
+       words, normal: 1 games, 1 wins (100%), 50.0s per game on average\n    2 attempts | # 1\n\
+       1 games with custom settings (not included above)"
+    );
+  }
+
+  #[test]
+  fn appends_and_loads_the_log() {
+    let path = std::env::temp_dir().join(format!("guessing-game-stats-{}.csv", std::process::id()));
+    let path = path.to_str().unwrap();
+    let _ = fs::remove_file(path);
+
+    assert_eq!(load(path).unwrap(), vec![]);
+    append(path, &record(Difficulty::Easy, true, 7, 15)).unwrap();
+    append(path, &record(Difficulty::Hard, false, 5, 40)).unwrap();
+
+    assert!(fs::read_to_string(path).unwrap().starts_with(HEADER));
+    assert_eq!(load(path).unwrap(), vec![record(Difficulty::Easy, true, 7, 15), record(Difficulty::Hard, false, 5, 40)]);
+    fs::remove_file(path).unwrap();
+  }
+
+  #[test]
+  fn rejects_logs_with_an_unknown_header() {
+    let path = std::env::temp_dir().join(format!("guessing-game-other-stats-{}.csv", std::process::id()));
+    let path = path.to_str().unwrap();
+    fs::write(path, "name,score\nMiguel,42\n").unwrap();
+
+    assert_eq!(load(path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    fs::remove_file(path).unwrap();
+  }
+}
@@ -0,0 +1,148 @@
+use std::ops::RangeInclusive;
+
+use crate::difficulty::GameSettings;
+use crate::game::{GameState, GuessResult};
+
+// A way of playing the game automatically. New strategies only need to implement this trait
+pub trait Strategy {
+  fn name(&self) -> &str;
+
+  fn next_guess(&mut self) -> u32;
+
+  // Called with the result of every guess, so the strategy can learn from it
+  fn feedback(&mut self, guess: u32, result: &GuessResult);
+}
+
+// Halves the candidate range after every guess. Needs "too small" / "too big" hints
+pub struct BinarySearch {
+  low: u32,
+  high: u32,
+}
+
+impl BinarySearch {
+  pub fn new(range: RangeInclusive<u32>) -> BinarySearch {
+    BinarySearch {
+      low: *range.start(),
+      high: *range.end(),
+    }
+  }
+}
+
+impl Strategy for BinarySearch {
+  fn name(&self) -> &str {
+    "binary search"
+  }
+
+  fn next_guess(&mut self) -> u32 {
+    // Same as (low + high) / 2, but can't overflow
+    self.low + (self.high - self.low) / 2
+  }
+
+  fn feedback(&mut self, guess: u32, result: &GuessResult) {
+    match result {
+      GuessResult::TooSmall => self.low = guess + 1,
+      GuessResult::TooBig => self.high = guess - 1,
+      GuessResult::Win | GuessResult::Lose => (),
+    }
+  }
+}
+
+// Tries every number in order. It is the best that can be done when there are no hints
+pub struct LinearScan {
+  next: u32,
+}
+
+impl LinearScan {
+  pub fn new(range: RangeInclusive<u32>) -> LinearScan {
+    LinearScan { next: *range.start() }
+  }
+}
+
+impl Strategy for LinearScan {
+  fn name(&self) -> &str {
+    "linear scan"
+  }
+
+  fn next_guess(&mut self) -> u32 {
+    let guess = self.next;
+    // The game is over once the last number of the range is guessed, so staying there is enough
+    self.next = self.next.saturating_add(1);
+    guess
+  }
+
+  fn feedback(&mut self, _guess: u32, _result: &GuessResult) {}
+}
+
+// Picks a strategy that only relies on the information the settings give to a human player
+pub fn for_settings(settings: &GameSettings) -> Box<dyn Strategy> {
+  if settings.hints {
+    Box::new(BinarySearch::new(settings.range()))
+  } else {
+    Box::new(LinearScan::new(settings.range()))
+  }
+}
+
+// Lets `strategy` play until the game is over. Returns every guess with its result
+pub fn play(strategy: &mut dyn Strategy, game: &mut GameState) -> Vec<(u32, GuessResult)> {
+  let mut steps = Vec::new();
+
+  loop {
+    let guess = strategy.next_guess();
+    let result = game.guess(guess);
+    strategy.feedback(guess, &result);
+
+    let is_over = matches!(result, GuessResult::Win | GuessResult::Lose);
+    steps.push((guess, result));
+    if is_over {
+      return steps;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn attempts_to_find(strategy: &mut dyn Strategy, secret_number: u32) -> u32 {
+    let mut game = GameState::new(secret_number, None);
+    let steps = play(strategy, &mut game);
+
+    assert_eq!(steps.last(), Some(&(secret_number, GuessResult::Win)));
+    game.attempts()
+  }
+
+  #[test]
+  fn binary_search_finds_any_number_in_log2_attempts() {
+    for secret_number in 1..=100 {
+      let attempts = attempts_to_find(&mut BinarySearch::new(1..=100), secret_number);
+      // 2^7 = 128 > 100
+      assert!(attempts <= 7, "{secret_number} took {attempts} attempts");
+    }
+  }
+
+  #[test]
+  fn binary_search_handles_the_bounds_of_the_range() {
+    assert!(attempts_to_find(&mut BinarySearch::new(0..=u32::MAX), 0) <= 32);
+    assert!(attempts_to_find(&mut BinarySearch::new(0..=u32::MAX), u32::MAX) <= 33);
+  }
+
+  #[test]
+  fn linear_scan_handles_the_end_of_the_range() {
+    assert_eq!(attempts_to_find(&mut LinearScan::new(u32::MAX - 1..=u32::MAX), u32::MAX), 2);
+  }
+
+  #[test]
+  fn linear_scan_tries_every_number_in_order() {
+    assert_eq!(attempts_to_find(&mut LinearScan::new(1..=20), 1), 1);
+    assert_eq!(attempts_to_find(&mut LinearScan::new(1..=20), 20), 20);
+  }
+
+  #[test]
+  fn stops_when_running_out_of_attempts() {
+    let mut game = GameState::new(20, Some(3));
+    let steps = play(&mut LinearScan::new(1..=20), &mut game);
+
+    assert_eq!(steps.len(), 3);
+    assert_eq!(steps.last(), Some(&(3, GuessResult::Lose)));
+  }
+}
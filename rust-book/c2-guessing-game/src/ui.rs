@@ -0,0 +1,271 @@
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crate::game::{Compact, Game};
+
+// Everything the UI needs to know about a game in progress. The game loop builds it from any `Game`,
+// so the UIs don't depend on the game variant
+pub struct View {
+  pub describe_guess: String,
+  pub attempts: u32,
+  pub remaining_attempts: Option<u32>,
+  pub elapsed: Duration,
+  pub time_left: Option<Duration>,
+  pub history: Vec<String>,
+}
+
+impl View {
+  pub fn of(game: &impl Game) -> View {
+    View {
+      describe_guess: game.describe_guess(),
+      attempts: game.attempts(),
+      remaining_attempts: game.remaining_attempts(),
+      elapsed: game.elapsed(),
+      time_left: game.time_left(),
+      history: game.history(),
+    }
+  }
+}
+
+// How a game is shown to the player. The game loop only decides *what* happened; each UI decides how it looks
+pub trait Ui {
+  // Extra information, e.g. the secret number in debug mode
+  fn note(&mut self, text: &str);
+
+  fn prompt(&mut self, view: &View);
+
+  fn invalid_input(&mut self, view: &View, message: &str);
+
+  fn wrong_guess(&mut self, view: &View, feedback: &dyn Compact);
+
+  fn game_over(&mut self, view: &View, won: bool, message: &str);
+
+  // Called about once per second while waiting for a guess in timed games
+  fn tick(&mut self, _view: &View) {}
+}
+
+// One line per event, which works when the output is piped to a file or another program
+pub struct PlainUi;
+
+impl PlainUi {
+  fn print_elapsed(view: &View) {
+    if view.time_left.is_some() {
+      println!("Time elapsed: {:.1}s", view.elapsed.as_secs_f64());
+    }
+  }
+}
+
+impl Ui for PlainUi {
+  fn note(&mut self, text: &str) {
+    println!("{text}");
+  }
+
+  fn prompt(&mut self, view: &View) {
+    match view.remaining_attempts {
+      Some(remaining) => println!(
+        "Please input your guess ({}, {remaining} attempts left).",
+        view.describe_guess
+      ),
+      None => println!("Please input your guess ({}).", view.describe_guess),
+    }
+  }
+
+  fn invalid_input(&mut self, _view: &View, message: &str) {
+    println!("{message}. Try again...");
+  }
+
+  fn wrong_guess(&mut self, view: &View, feedback: &dyn Compact) {
+    PlainUi::print_elapsed(view);
+    println!("{feedback}");
+  }
+
+  fn game_over(&mut self, view: &View, won: bool, message: &str) {
+    if won {
+      PlainUi::print_elapsed(view);
+    }
+    println!("{message}");
+    println!("Your guesses: {}", view.history.join(", "));
+  }
+}
+
+// ANSI escape codes, understood by most terminals
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+const SAVE_CURSOR: &str = "\x1b7";
+const RESTORE_CURSOR: &str = "\x1b8";
+const CLEAR_LINE: &str = "\x1b[2K";
+// The status line (attempts and timer) is always the second line of the screen
+const STATUS_ROW: usize = 2;
+// Only the last guesses fit on the screen when the attempts are unlimited
+const MAX_HISTORY_LINES: usize = 10;
+
+// Redraws the whole screen on every guess: status, guess history and last message
+pub struct TerminalUi {
+  title: String,
+  notes: Vec<String>,
+  // Each guess with the compact form of its feedback (e.g. an arrow)
+  history: Vec<(String, String)>,
+  message: String,
+}
+
+impl TerminalUi {
+  pub fn new(title: &str) -> TerminalUi {
+    TerminalUi {
+      title: String::from(title),
+      notes: Vec::new(),
+      history: Vec::new(),
+      message: String::new(),
+    }
+  }
+
+  fn status(view: &View) -> String {
+    let attempts = match view.remaining_attempts {
+      Some(remaining) => format!("Attempts left: {remaining}"),
+      None => format!("Attempts: {}", view.attempts),
+    };
+    match view.time_left {
+      Some(time_left) => format!(
+        "{attempts} | Time: {:.1}s ({:.0}s left)",
+        view.elapsed.as_secs_f64(),
+        time_left.as_secs_f64()
+      ),
+      None => attempts,
+    }
+  }
+
+  fn screen(&self, view: &View) -> String {
+    let mut screen = format!("{CLEAR_SCREEN}** {} **\n{}\n\n", self.title, TerminalUi::status(view));
+    for note in &self.notes {
+      screen.push_str(&format!("{note}\n"));
+    }
+
+    screen.push_str("Guesses:\n");
+    let skipped = self.history.len().saturating_sub(MAX_HISTORY_LINES);
+    if skipped > 0 {
+      screen.push_str(&format!("  ({skipped} more)\n"));
+    }
+    for (guess, feedback) in self.history.iter().skip(skipped) {
+      screen.push_str(&format!("  {guess:>6}  {feedback}\n"));
+    }
+
+    screen.push_str(&format!("\n{}\n", self.message));
+    screen
+  }
+
+  fn draw(&self, view: &View) {
+    print!("{}", self.screen(view));
+    io::stdout().flush().expect("Failed to write to stdout");
+  }
+
+  fn last_guess(view: &View) -> String {
+    view.history.last().cloned().unwrap_or_default()
+  }
+}
+
+impl Ui for TerminalUi {
+  fn note(&mut self, text: &str) {
+    self.notes.push(String::from(text));
+  }
+
+  fn prompt(&mut self, view: &View) {
+    self.draw(view);
+    // No new line: the player types next to the prompt
+    print!("Your guess ({}): ", view.describe_guess);
+    io::stdout().flush().expect("Failed to write to stdout");
+  }
+
+  fn invalid_input(&mut self, _view: &View, message: &str) {
+    self.message = format!("{message}. Try again...");
+  }
+
+  fn wrong_guess(&mut self, view: &View, feedback: &dyn Compact) {
+    self.history.push((TerminalUi::last_guess(view), feedback.compact()));
+    self.message = feedback.to_string();
+  }
+
+  fn game_over(&mut self, view: &View, won: bool, message: &str) {
+    // The last guess ended the game, so it never went through `wrong_guess`. When the time runs out
+    // there is no new guess to add
+    if view.history.len() > self.history.len() {
+      let mark = if won { "✓" } else { "✗" };
+      self.history.push((TerminalUi::last_guess(view), String::from(mark)));
+    }
+    self.message = String::from(message);
+    self.draw(view);
+  }
+
+  // Only rewrites the status line, so that whatever the player is typing isn't erased
+  fn tick(&mut self, view: &View) {
+    print!(
+      "{SAVE_CURSOR}\x1b[{STATUS_ROW};1H{CLEAR_LINE}{}{RESTORE_CURSOR}",
+      TerminalUi::status(view)
+    );
+    io::stdout().flush().expect("Failed to write to stdout");
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn view(remaining_attempts: Option<u32>, time_left: Option<Duration>) -> View {
+    View {
+      describe_guess: String::from("between 1 and 100"),
+      attempts: 2,
+      remaining_attempts,
+      elapsed: Duration::from_millis(3200),
+      time_left,
+      history: vec![String::from("50"), String::from("75")],
+    }
+  }
+
+  #[test]
+  fn status_shows_attempts_and_timer() {
+    assert_eq!(TerminalUi::status(&view(Some(8), None)), "Attempts left: 8");
+    assert_eq!(TerminalUi::status(&view(None, None)), "Attempts: 2");
+    assert_eq!(
+      TerminalUi::status(&view(Some(8), Some(Duration::from_secs(27)))),
+      "Attempts left: 8 | Time: 3.2s (27s left)"
+    );
+  }
+
+  #[test]
+  fn screen_shows_the_history_with_feedback() {
+    let mut ui = TerminalUi::new("Guessing game");
+    ui.history.push((String::from("50"), String::from("↑")));
+    ui.history.push((String::from("75"), String::from("↓")));
+    ui.message = String::from("Too big!");
+
+    let screen = ui.screen(&view(Some(8), None));
+    let lines: Vec<&str> = screen.trim_start_matches(CLEAR_SCREEN).lines().collect();
+    assert_eq!(
+      lines,
+      vec!["** Guessing game **", "Attempts left: 8", "", "Guesses:", "      50  ↑", "      75  ↓", "", "Too big!"]
+    );
+  }
+
+  #[test]
+  fn screen_only_shows_the_last_guesses() {
+    let mut ui = TerminalUi::new("Guessing game");
+    for guess in 1..=15 {
+      ui.history.push((guess.to_string(), String::from("↑")));
+    }
+
+    let screen = ui.screen(&view(None, None));
+    assert!(screen.contains("(5 more)"));
+    assert!(!screen.contains("     5  ↑"));
+    assert!(screen.contains("     6  ↑"));
+  }
+
+  #[test]
+  fn game_over_adds_the_last_guess_to_the_history() {
+    let mut ui = TerminalUi::new("Guessing game");
+    ui.history.push((String::from("50"), String::from("↑")));
+
+    ui.game_over(&view(Some(0), None), false, "No attempts left. You lose!");
+    assert_eq!(ui.history[1], (String::from("75"), String::from("✗")));
+
+    // Time's up: no new guess since the last one
+    ui.game_over(&view(Some(0), None), false, "Time's up! You lose!");
+    assert_eq!(ui.history.len(), 2);
+  }
+}
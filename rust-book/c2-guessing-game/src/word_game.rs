@@ -0,0 +1,229 @@
+use std::fmt;
+use rand::seq::SliceRandom;
+
+use crate::difficulty::GameSettings;
+use crate::game::{Compact, Game, Limits, Turn};
+
+// The list is compiled into the binary, so the game works from any directory
+const WORDS: &str = include_str!("words.txt");
+const WORD_LENGTH: usize = 5;
+
+pub fn random_word() -> String {
+  let words: Vec<&str> = WORDS.lines().map(str::trim).filter(|word| !word.is_empty()).collect();
+  words
+    .choose(&mut rand::thread_rng())
+    .expect("The word list is empty")
+    .to_string()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LetterResult {
+  // Right letter in the right position
+  Correct,
+  // The letter is in the word, but in another position
+  Present,
+  Absent,
+}
+
+// Compares a guess with the secret word, letter by letter. Both must have the same length.
+// A letter repeated in the guess is only marked as present as many times as it appears in the secret
+pub fn compare(guess: &str, secret: &str) -> Vec<LetterResult> {
+  let guess: Vec<char> = guess.chars().collect();
+  let secret: Vec<char> = secret.chars().collect();
+  let mut results = vec![LetterResult::Absent; guess.len()];
+  // Letters of the secret that weren't matched in their position, available to mark others as present
+  let mut unmatched = Vec::new();
+
+  for (i, (g, s)) in guess.iter().zip(&secret).enumerate() {
+    if g == s {
+      results[i] = LetterResult::Correct;
+    } else {
+      unmatched.push(*s);
+    }
+  }
+
+  for (i, g) in guess.iter().enumerate() {
+    if results[i] == LetterResult::Correct {
+      continue;
+    }
+    if let Some(pos) = unmatched.iter().position(|s| s == g) {
+      unmatched.swap_remove(pos);
+      results[i] = LetterResult::Present;
+    }
+  }
+
+  results
+}
+
+pub struct WordFeedback {
+  pub letters: Vec<(char, LetterResult)>,
+}
+
+impl WordFeedback {
+  // `=` correct, `~` present, `.` absent
+  fn symbols(&self) -> Vec<&str> {
+    self
+      .letters
+      .iter()
+      .map(|(_, result)| match result {
+        LetterResult::Correct => "=",
+        LetterResult::Present => "~",
+        LetterResult::Absent => ".",
+      })
+      .collect()
+  }
+}
+
+// Prints the guess in capitals, with the symbol of each letter under it
+impl fmt::Display for WordFeedback {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let letters: Vec<String> = self.letters.iter().map(|(c, _)| c.to_ascii_uppercase().to_string()).collect();
+    write!(f, "{}\n{}", letters.join(" "), self.symbols().join(" "))
+  }
+}
+
+impl Compact for WordFeedback {
+  fn compact(&self) -> String {
+    self.symbols().concat()
+  }
+}
+
+pub struct WordGame {
+  secret: String,
+  limits: Limits,
+  history: Vec<String>,
+  // Without hints, letters in the wrong position are reported as absent
+  hints: bool,
+}
+
+impl WordGame {
+  pub fn new(secret: String, settings: &GameSettings) -> WordGame {
+    WordGame {
+      secret,
+      limits: Limits::new(settings.max_attempts, settings.time_limit),
+      history: Vec::new(),
+      hints: settings.hints,
+    }
+  }
+}
+
+impl Game for WordGame {
+  type Feedback = WordFeedback;
+
+  fn describe_guess(&self) -> String {
+    format!("a word of {WORD_LENGTH} letters")
+  }
+
+  fn play_turn(&mut self, input: &str) -> Result<Turn<WordFeedback>, String> {
+    let guess = input.trim().to_lowercase();
+    if guess.len() != WORD_LENGTH || !guess.chars().all(|c| c.is_ascii_alphabetic()) {
+      return Err(format!("Your guess must be {}", self.describe_guess()));
+    }
+
+    let results = compare(&guess, &self.secret);
+    self.history.push(guess.clone());
+
+    if guess == self.secret {
+      return Ok(Turn::Win);
+    }
+    if self.remaining_attempts() == Some(0) {
+      return Ok(Turn::Lose);
+    }
+
+    let letters = guess
+      .chars()
+      .zip(results)
+      .map(|(c, result)| match result {
+        LetterResult::Present if !self.hints => (c, LetterResult::Absent),
+        _ => (c, result),
+      })
+      .collect();
+    Ok(Turn::Wrong(WordFeedback { letters }))
+  }
+
+  fn reveal(&self) -> String {
+    format!("The secret word was '{}'.", self.secret)
+  }
+
+  fn history(&self) -> Vec<String> {
+    self.history.clone()
+  }
+
+  fn attempts(&self) -> u32 {
+    self.history.len() as u32
+  }
+
+  fn limits(&self) -> &Limits {
+    &self.limits
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use LetterResult::{Absent, Correct, Present};
+
+  fn word_game(secret: &str, hints: bool) -> WordGame {
+    let settings = GameSettings { min: 1, max: 100, max_attempts: Some(2), hints, time_limit: None };
+    WordGame::new(String::from(secret), &settings)
+  }
+
+  #[test]
+  fn bundled_words_are_valid_guesses() {
+    for word in WORDS.lines() {
+      assert!(word_game("crane", true).play_turn(word).is_ok(), "invalid bundled word '{word}'");
+    }
+  }
+
+  #[test]
+  fn compares_letters_and_positions() {
+    assert_eq!(compare("crane", "crane"), vec![Correct; 5]);
+    assert_eq!(compare("plumb", "crane"), vec![Absent; 5]);
+    assert_eq!(compare("nacre", "crane"), vec![Present, Present, Present, Present, Correct]);
+  }
+
+  #[test]
+  fn repeated_letters_are_only_present_as_many_times_as_in_the_secret() {
+    // "geese" has three e's and "sheep" two: one is in place, one elsewhere, and the last one is extra
+    assert_eq!(compare("geese", "sheep"), vec![Absent, Present, Correct, Present, Absent]);
+    // Only one 'o' in "robot" is left after matching the one in place
+    assert_eq!(compare("ooooo", "robot"), vec![Absent, Correct, Absent, Correct, Absent]);
+  }
+
+  #[test]
+  fn rejects_guesses_that_are_not_words_of_the_right_length() {
+    let mut game = word_game("crane", true);
+
+    assert!(game.play_turn("cat").is_err());
+    assert!(game.play_turn("cr4ne").is_err());
+    assert_eq!(game.attempts(), 0);
+  }
+
+  #[test]
+  fn gives_feedback_until_the_word_is_found() {
+    let mut game = word_game("crane", true);
+
+    let Ok(Turn::Wrong(feedback)) = game.play_turn("Nacre\n") else { panic!("expected a wrong guess") };
+    assert_eq!(feedback.to_string(), "N A C R E\n~ ~ ~ ~ =");
+    assert_eq!(feedback.compact(), "~~~~=");
+    assert!(matches!(game.play_turn("crane"), Ok(Turn::Win)));
+    assert_eq!(game.history(), vec!["nacre", "crane"]);
+  }
+
+  #[test]
+  fn without_hints_misplaced_letters_look_absent() {
+    let mut game = word_game("crane", false);
+
+    let Ok(Turn::Wrong(feedback)) = game.play_turn("nacre") else { panic!("expected a wrong guess") };
+    assert_eq!(feedback.to_string(), "N A C R E\n. . . . =");
+  }
+
+  #[test]
+  fn loses_after_max_attempts() {
+    let mut game = word_game("crane", true);
+
+    game.play_turn("plumb").unwrap();
+    assert!(matches!(game.play_turn("plumb"), Ok(Turn::Lose)));
+    assert_eq!(game.reveal(), "The secret word was 'crane'.");
+  }
+}
@@ -0,0 +1,91 @@
+pub mod inventory;
+pub mod menu;
+
+use serde::{Deserialize, Serialize};
+
+use menu::{Course, MenuItem, Season};
+
+pub struct Breakfast {
+  pub toast: String,
+  seasonal_fruit: String,
+}
+
+impl Breakfast {
+  // The customer chooses the toast, and the kitchen the fruit (see `menu::seasonal_fruit`)
+  pub fn for_season(season: Season, toast: &str) -> Breakfast {
+    Breakfast {
+      toast: String::from(toast),
+      seasonal_fruit: String::from(menu::seasonal_fruit(season)),
+    }
+  }
+
+  // Shortcuts for each season
+  pub fn spring(toast: &str) -> Breakfast {
+    Breakfast::for_season(Season::Spring, toast)
+  }
+
+  pub fn summer(toast: &str) -> Breakfast {
+    Breakfast::for_season(Season::Summer, toast)
+  }
+
+  pub fn autumn(toast: &str) -> Breakfast {
+    Breakfast::for_season(Season::Autumn, toast)
+  }
+
+  pub fn winter(toast: &str) -> Breakfast {
+    Breakfast::for_season(Season::Winter, toast)
+  }
+
+  // The breakfast as it's ordered. Its name tells the fruit, but it still can't be changed
+  pub fn menu_item(&self) -> MenuItem {
+    let name = format!("Breakfast with {} toast and {}", self.toast, self.seasonal_fruit);
+    MenuItem::new(&name, menu::BREAKFAST_PRICE, Course::Breakfast)
+  }
+}
+
+pub enum Appetizer {
+  Soup,
+  Salad,
+  FreeAppetizerOnTheHouse
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Client {
+  pub name: String,
+  pub phone: String
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn each_season_comes_with_its_fruit() {
+    let fruits = [
+      (Season::Spring, "strawberries"),
+      (Season::Summer, "peaches"),
+      (Season::Autumn, "apples"),
+      (Season::Winter, "oranges"),
+    ];
+    for (season, fruit) in fruits {
+      assert_eq!(Breakfast::for_season(season, "Rye").seasonal_fruit, fruit);
+    }
+  }
+
+  #[test]
+  fn shortcuts_match_their_season() {
+    let shortcuts = [Breakfast::spring, Breakfast::summer, Breakfast::autumn, Breakfast::winter];
+    for (season, shortcut) in Season::ALL.into_iter().zip(shortcuts) {
+      let breakfast = shortcut("Wheat");
+      assert_eq!(breakfast.toast, "Wheat");
+      assert_eq!(breakfast.seasonal_fruit, Breakfast::for_season(season, "Wheat").seasonal_fruit);
+    }
+  }
+
+  #[test]
+  fn breakfast_is_a_menu_item() {
+    let item = Breakfast::summer("Wheat").menu_item();
+    assert_eq!(item.name, "Breakfast with Wheat toast and peaches");
+    assert_eq!(item.course, Course::Breakfast);
+  }
+}
@@ -0,0 +1,211 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use super::menu::MenuItem;
+
+#[derive(Debug, PartialEq)]
+pub enum InventoryError {
+  // The kitchen doesn't know how to make the item
+  NoRecipe(String),
+  // `needed` is a `u64` because it adds up the quantities of several items, which may not fit in a `u32`
+  OutOfStock { ingredient: String, needed: u64, available: u32 },
+}
+
+impl fmt::Display for InventoryError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      InventoryError::NoRecipe(item) => write!(f, "there is no recipe for '{item}'"),
+      InventoryError::OutOfStock { ingredient, needed, available } => {
+        write!(f, "not enough {ingredient}: {needed} needed, {available} left")
+      }
+    }
+  }
+}
+
+#[derive(Debug)]
+struct Stock {
+  quantity: u32,
+  // An alert is raised when the quantity goes down to this value
+  low_threshold: u32,
+}
+
+// Quantities are in whatever unit makes sense for each ingredient (grams, eggs, slices...)
+#[derive(Debug, Default)]
+pub struct Inventory {
+  // Sorted by name, so that the alerts always come out in the same order
+  stock: BTreeMap<String, Stock>,
+  // Ingredients consumed by each menu item, by item name
+  recipes: HashMap<String, Vec<(String, u32)>>,
+}
+
+impl Inventory {
+  pub fn new() -> Inventory {
+    Inventory::default()
+  }
+
+  // Adds `quantity` to the stock of the ingredient, and sets the level below which it's running low.
+  // The stock stops growing at `u32::MAX`, which is more than any kitchen can hold
+  pub fn restock(&mut self, ingredient: &str, quantity: u32, low_threshold: u32) {
+    let stock = self
+      .stock
+      .entry(String::from(ingredient))
+      .or_insert(Stock { quantity: 0, low_threshold });
+    stock.quantity = stock.quantity.saturating_add(quantity);
+    stock.low_threshold = low_threshold;
+  }
+
+  pub fn quantity(&self, ingredient: &str) -> u32 {
+    self.stock.get(ingredient).map_or(0, |stock| stock.quantity)
+  }
+
+  pub fn set_recipe(&mut self, item: &str, ingredients: &[(&str, u32)]) {
+    let ingredients = ingredients
+      .iter()
+      .map(|(ingredient, quantity)| (ingredient.to_string(), *quantity))
+      .collect();
+    self.recipes.insert(String::from(item), ingredients);
+  }
+
+  // Whether there are enough ingredients to make the item once
+  pub fn is_available(&self, item: &MenuItem) -> bool {
+    self.check(std::slice::from_ref(item)).is_ok()
+  }
+
+  // Takes the ingredients of all the items from the stock. If anything is missing, nothing is taken
+  pub fn consume(&mut self, items: &[MenuItem]) -> Result<(), InventoryError> {
+    for (ingredient, needed) in self.check(items)? {
+      if let Some(stock) = self.stock.get_mut(&ingredient) {
+        stock.quantity -= needed;
+      }
+    }
+    Ok(())
+  }
+
+  // Ingredients at or below their threshold, with the quantity left
+  pub fn low_stock(&self) -> Vec<(&str, u32)> {
+    self
+      .stock
+      .iter()
+      .filter(|(_, stock)| stock.quantity <= stock.low_threshold)
+      .map(|(ingredient, stock)| (ingredient.as_str(), stock.quantity))
+      .collect()
+  }
+
+  // Adds up the ingredients needed by all the items, and checks that there are enough of each.
+  // They are checked by name, so that the ingredient reported when several are missing is always the same
+  fn check(&self, items: &[MenuItem]) -> Result<BTreeMap<String, u32>, InventoryError> {
+    let mut needed: BTreeMap<String, u64> = BTreeMap::new();
+    for item in items {
+      let recipe = self
+        .recipes
+        .get(&item.name)
+        .ok_or_else(|| InventoryError::NoRecipe(item.name.clone()))?;
+      for (ingredient, quantity) in recipe {
+        *needed.entry(ingredient.clone()).or_insert(0) += u64::from(*quantity);
+      }
+    }
+
+    let mut checked = BTreeMap::new();
+    for (ingredient, needed) in needed {
+      let available = self.quantity(&ingredient);
+      // Never more than what's available, so it fits in a `u32`
+      match u32::try_from(needed) {
+        Ok(needed) if needed <= available => checked.insert(ingredient, needed),
+        _ => return Err(InventoryError::OutOfStock { ingredient, needed, available }),
+      };
+    }
+    Ok(checked)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::back_of_house::menu::Course;
+  use crate::billing::Cents;
+
+  fn pancakes() -> MenuItem {
+    MenuItem::new("Pancakes", Cents(650), Course::Breakfast)
+  }
+
+  fn inventory() -> Inventory {
+    let mut inventory = Inventory::new();
+    inventory.restock("eggs", 5, 2);
+    inventory.restock("flour", 1000, 200);
+    inventory.set_recipe("Pancakes", &[("eggs", 2), ("flour", 150)]);
+    inventory
+  }
+
+  #[test]
+  fn consumes_the_ingredients_of_the_items() {
+    let mut inventory = inventory();
+
+    inventory.consume(&[pancakes(), pancakes()]).unwrap();
+    assert_eq!(inventory.quantity("eggs"), 1);
+    assert_eq!(inventory.quantity("flour"), 700);
+  }
+
+  #[test]
+  fn takes_nothing_when_an_ingredient_runs_out() {
+    let mut inventory = inventory();
+
+    assert_eq!(
+      inventory.consume(&[pancakes(), pancakes(), pancakes()]),
+      Err(InventoryError::OutOfStock { ingredient: String::from("eggs"), needed: 6, available: 5 })
+    );
+    assert_eq!(inventory.quantity("eggs"), 5);
+    assert_eq!(inventory.quantity("flour"), 1000);
+  }
+
+  #[test]
+  fn reports_the_first_missing_ingredient_by_name() {
+    let mut inventory = inventory();
+    inventory.restock("milk", 100, 50);
+    inventory.set_recipe("Crepes", &[("milk", 300), ("flour", 2000), ("eggs", 6)]);
+    let crepes = MenuItem::new("Crepes", Cents(700), Course::Dessert);
+
+    assert_eq!(
+      inventory.consume(&[crepes]),
+      Err(InventoryError::OutOfStock { ingredient: String::from("eggs"), needed: 6, available: 5 })
+    );
+  }
+
+  #[test]
+  fn quantities_never_overflow() {
+    let mut inventory = inventory();
+    inventory.restock("eggs", u32::MAX, 2);
+    assert_eq!(inventory.quantity("eggs"), u32::MAX);
+
+    inventory.set_recipe("Omelette", &[("eggs", u32::MAX)]);
+    let omelette = MenuItem::new("Omelette", Cents(800), Course::Main);
+    assert_eq!(
+      inventory.consume(&[omelette.clone(), omelette]),
+      Err(InventoryError::OutOfStock { ingredient: String::from("eggs"), needed: 2 * u64::from(u32::MAX), available: u32::MAX })
+    );
+  }
+
+  #[test]
+  fn items_without_recipe_or_ingredients_are_unavailable() {
+    let mut inventory = inventory();
+    let soup = MenuItem::new("Soup", Cents(500), Course::Starter);
+
+    assert!(inventory.is_available(&pancakes()));
+    assert!(!inventory.is_available(&soup));
+    assert_eq!(inventory.consume(&[soup]), Err(InventoryError::NoRecipe(String::from("Soup"))));
+
+    inventory.consume(&[pancakes(), pancakes()]).unwrap();
+    assert!(!inventory.is_available(&pancakes()));
+  }
+
+  #[test]
+  fn alerts_when_stock_is_low() {
+    let mut inventory = inventory();
+    assert!(inventory.low_stock().is_empty());
+
+    inventory.consume(&[pancakes(), pancakes()]).unwrap();
+    assert_eq!(inventory.low_stock(), vec![("eggs", 1)]);
+
+    inventory.restock("eggs", 12, 2);
+    assert!(inventory.low_stock().is_empty());
+  }
+}
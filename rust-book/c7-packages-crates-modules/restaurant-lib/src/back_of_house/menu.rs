@@ -0,0 +1,258 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::billing::Cents;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Course {
+  Breakfast,
+  Starter,
+  Main,
+  Dessert,
+  Drink,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Allergen {
+  Gluten,
+  Dairy,
+  Eggs,
+  Nuts,
+  Fish,
+  Soy,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MenuItem {
+  // Unique in a menu: it's what waiters and customers use to refer to the item
+  pub name: String,
+  pub price: Cents,
+  pub course: Course,
+  pub allergens: Vec<Allergen>,
+}
+
+impl MenuItem {
+  pub fn new(name: &str, price: Cents, course: Course) -> MenuItem {
+    MenuItem {
+      name: String::from(name),
+      price,
+      course,
+      allergens: Vec::new(),
+    }
+  }
+
+  // Builder-style method, so that items can be declared in one expression:
+  // `MenuItem::new("Pancakes", Cents(650), Course::Breakfast).with_allergens(&[Allergen::Gluten])`
+  pub fn with_allergens(mut self, allergens: &[Allergen]) -> MenuItem {
+    self.allergens = allergens.to_vec();
+    self
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MenuError {
+  DuplicateItem(String),
+  UnknownItem(String),
+}
+
+impl fmt::Display for MenuError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      MenuError::DuplicateItem(name) => write!(f, "'{name}' is already on the menu"),
+      MenuError::UnknownItem(name) => write!(f, "'{name}' is not on the menu"),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Season {
+  Spring,
+  Summer,
+  Autumn,
+  Winter,
+}
+
+impl Season {
+  pub const ALL: [Season; 4] = [Season::Spring, Season::Summer, Season::Autumn, Season::Winter];
+}
+
+// The fruit served with the breakfast in each season. `Breakfast::for_season` reads it from here,
+// so changing the menu doesn't require touching the kitchen code
+pub fn seasonal_fruit(season: Season) -> &'static str {
+  match season {
+    Season::Spring => "strawberries",
+    Season::Summer => "peaches",
+    Season::Autumn => "apples",
+    Season::Winter => "oranges",
+  }
+}
+
+// Whatever the season
+pub const BREAKFAST_PRICE: Cents = Cents(950);
+
+// Read through `UncheckedMenu`, so that a file can't give a menu that `add` and `set_daily_specials` would refuse
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedMenu")]
+pub struct Menu {
+  // A `Vec` (and not a `HashMap`) keeps the items in the order they were added, which is how they are printed
+  items: Vec<MenuItem>,
+  // Names of the items that are the specials of the day
+  daily_specials: Vec<String>,
+}
+
+// The fields of a `Menu` as they are in a file, before checking them
+#[derive(Deserialize)]
+struct UncheckedMenu {
+  items: Vec<MenuItem>,
+  daily_specials: Vec<String>,
+}
+
+impl TryFrom<UncheckedMenu> for Menu {
+  type Error = MenuError;
+
+  fn try_from(unchecked: UncheckedMenu) -> Result<Menu, MenuError> {
+    let mut menu = Menu::new();
+    for item in unchecked.items {
+      menu.add(item)?;
+    }
+    let specials: Vec<&str> = unchecked.daily_specials.iter().map(String::as_str).collect();
+    menu.set_daily_specials(&specials)?;
+    Ok(menu)
+  }
+}
+
+impl Menu {
+  pub fn new() -> Menu {
+    Menu::default()
+  }
+
+  pub fn add(&mut self, item: MenuItem) -> Result<(), MenuError> {
+    if self.get(&item.name).is_some() {
+      return Err(MenuError::DuplicateItem(item.name));
+    }
+    self.items.push(item);
+    Ok(())
+  }
+
+  // Removes the item (also from the daily specials) and gives it back
+  pub fn remove(&mut self, name: &str) -> Result<MenuItem, MenuError> {
+    let index = self.position(name)?;
+    self.daily_specials.retain(|special| special != name);
+    Ok(self.items.remove(index))
+  }
+
+  // Replaces the item called `name` with `item`, keeping its place in the menu. Returns the old item
+  pub fn update(&mut self, name: &str, item: MenuItem) -> Result<MenuItem, MenuError> {
+    let index = self.position(name)?;
+    if item.name != name && self.get(&item.name).is_some() {
+      return Err(MenuError::DuplicateItem(item.name));
+    }
+
+    for special in self.daily_specials.iter_mut().filter(|special| *special == name) {
+      special.clone_from(&item.name);
+    }
+    Ok(std::mem::replace(&mut self.items[index], item))
+  }
+
+  pub fn get(&self, name: &str) -> Option<&MenuItem> {
+    self.items.iter().find(|item| item.name == name)
+  }
+
+  pub fn items(&self) -> &[MenuItem] {
+    &self.items
+  }
+
+  pub fn by_course(&self, course: Course) -> Vec<&MenuItem> {
+    self.items.iter().filter(|item| item.course == course).collect()
+  }
+
+  // Replaces the specials of the previous day. All of them must be on the menu
+  pub fn set_daily_specials(&mut self, names: &[&str]) -> Result<(), MenuError> {
+    if let Some(unknown) = names.iter().find(|name| self.get(name).is_none()) {
+      return Err(MenuError::UnknownItem(unknown.to_string()));
+    }
+    self.daily_specials = names.iter().map(|name| name.to_string()).collect();
+    Ok(())
+  }
+
+  pub fn daily_specials(&self) -> Vec<&MenuItem> {
+    self.daily_specials.iter().filter_map(|name| self.get(name)).collect()
+  }
+
+  fn position(&self, name: &str) -> Result<usize, MenuError> {
+    self
+      .items
+      .iter()
+      .position(|item| item.name == name)
+      .ok_or_else(|| MenuError::UnknownItem(String::from(name)))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn menu() -> Menu {
+    let mut menu = Menu::new();
+    menu.add(MenuItem::new("Pancakes", Cents(650), Course::Breakfast).with_allergens(&[Allergen::Gluten, Allergen::Eggs])).unwrap();
+    menu.add(MenuItem::new("Soup", Cents(500), Course::Starter)).unwrap();
+    menu.add(MenuItem::new("Salmon", Cents(1800), Course::Main).with_allergens(&[Allergen::Fish])).unwrap();
+    menu.add(MenuItem::new("Risotto", Cents(1500), Course::Main).with_allergens(&[Allergen::Dairy])).unwrap();
+    menu
+  }
+
+  #[test]
+  fn adds_items_with_unique_names() {
+    let mut menu = menu();
+
+    assert_eq!(menu.items().len(), 4);
+    assert_eq!(menu.get("Salmon").unwrap().allergens, vec![Allergen::Fish]);
+    assert_eq!(
+      menu.add(MenuItem::new("Soup", Cents(450), Course::Starter)),
+      Err(MenuError::DuplicateItem(String::from("Soup")))
+    );
+  }
+
+  #[test]
+  fn looks_up_items_by_course() {
+    let menu = menu();
+
+    let mains: Vec<&str> = menu.by_course(Course::Main).iter().map(|item| item.name.as_str()).collect();
+    assert_eq!(mains, vec!["Salmon", "Risotto"]);
+    assert!(menu.by_course(Course::Dessert).is_empty());
+  }
+
+  #[test]
+  fn updates_items_in_place() {
+    let mut menu = menu();
+    menu.set_daily_specials(&["Soup"]).unwrap();
+
+    let old = menu.update("Soup", MenuItem::new("Gazpacho", Cents(550), Course::Starter)).unwrap();
+    assert_eq!(old.price, Cents(500));
+    assert_eq!(menu.items()[1].name, "Gazpacho");
+    assert_eq!(menu.daily_specials()[0].name, "Gazpacho");
+
+    assert!(matches!(menu.update("Gazpacho", MenuItem::new("Salmon", Cents(1), Course::Main)), Err(MenuError::DuplicateItem(_))));
+    assert!(matches!(menu.update("Pizza", MenuItem::new("Pizza", Cents(1), Course::Main)), Err(MenuError::UnknownItem(_))));
+  }
+
+  #[test]
+  fn removing_an_item_removes_it_from_the_specials() {
+    let mut menu = menu();
+    menu.set_daily_specials(&["Salmon", "Risotto"]).unwrap();
+
+    assert_eq!(menu.remove("Salmon").unwrap().name, "Salmon");
+    let specials: Vec<&str> = menu.daily_specials().iter().map(|item| item.name.as_str()).collect();
+    assert_eq!(specials, vec!["Risotto"]);
+    assert_eq!(menu.remove("Salmon"), Err(MenuError::UnknownItem(String::from("Salmon"))));
+  }
+
+  #[test]
+  fn specials_must_be_on_the_menu() {
+    let mut menu = menu();
+
+    assert_eq!(menu.set_daily_specials(&["Soup", "Pizza"]), Err(MenuError::UnknownItem(String::from("Pizza"))));
+    assert!(menu.daily_specials().is_empty());
+  }
+}
@@ -0,0 +1,292 @@
+use std::fmt;
+use std::iter::Sum;
+use std::num::NonZeroU32;
+use std::ops::{Add, Mul, Sub};
+
+use serde::{Deserialize, Serialize};
+
+use crate::customer::Tier;
+use crate::front_of_house::orders::Order;
+
+// An amount of money. Using whole cents (instead of an `f64` of euros) means that adding prices
+// never gives results like 0.30000000000000004, and that the newtype can't be mixed up with other numbers.
+// In JSON it's just the number of cents, e.g. `1250`.
+// A `u64` because a `u32` only goes up to about 43 million euros, which a few big bills could reach
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Cents(pub u64);
+
+impl Cents {
+  // Rounded to the nearest cent (half a cent rounds up). Computed in `u128`, so that only
+  // results that don't fit in `Cents` (which no real bill reaches) panic
+  pub fn percent(self, percent: u32) -> Cents {
+    let cents = (u128::from(self.0) * u128::from(percent) + 50) / 100;
+    Cents(u64::try_from(cents).expect("amount of money too large"))
+  }
+}
+
+impl Add for Cents {
+  type Output = Cents;
+
+  fn add(self, other: Cents) -> Cents {
+    Cents(self.0.checked_add(other.0).expect("amount of money too large"))
+  }
+}
+
+impl Sub for Cents {
+  type Output = Cents;
+
+  fn sub(self, other: Cents) -> Cents {
+    Cents(self.0.checked_sub(other.0).expect("amount of money can't be negative"))
+  }
+}
+
+impl Mul<u32> for Cents {
+  type Output = Cents;
+
+  fn mul(self, quantity: u32) -> Cents {
+    Cents(self.0.checked_mul(quantity.into()).expect("amount of money too large"))
+  }
+}
+
+// Allows `.sum()` on iterators of `Cents`
+impl Sum for Cents {
+  fn sum<I: Iterator<Item = Cents>>(iter: I) -> Cents {
+    iter.fold(Cents(0), Add::add)
+  }
+}
+
+impl fmt::Display for Cents {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    // Formatting to a string first, so that callers can align it with `{:>8}`
+    f.pad(&format!("{}.{:02}", self.0 / 100, self.0 % 100))
+  }
+}
+
+pub enum Tip {
+  // Of the subtotal, before taxes
+  Percent(u32),
+  Amount(Cents),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BillLine {
+  pub name: String,
+  pub quantity: u32,
+  pub unit_price: Cents,
+}
+
+impl BillLine {
+  pub fn total(&self) -> Cents {
+    self.unit_price * self.quantity
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Bill {
+  pub table: u8,
+  // One line per menu item, in the order they were first ordered
+  pub lines: Vec<BillLine>,
+  pub tax_rate: u32,
+  // Loyalty discount, as a percentage of the subtotal
+  pub discount_percent: u32,
+  pub tip: Cents,
+  // `NonZeroU32`, because somebody has to pay
+  pub guests: NonZeroU32,
+}
+
+impl Bill {
+  // `tax_rate` is a percentage, e.g. 10 for 10%
+  pub fn for_order(order: &Order, tax_rate: u32) -> Bill {
+    let mut lines: Vec<BillLine> = Vec::new();
+    for item in order.items() {
+      match lines.iter_mut().find(|line| line.name == item.name) {
+        Some(line) => line.quantity += 1,
+        None => lines.push(BillLine { name: item.name.clone(), quantity: 1, unit_price: item.price }),
+      }
+    }
+
+    Bill { table: order.table, lines, tax_rate, discount_percent: 0, tip: Cents(0), guests: NonZeroU32::MIN }
+  }
+
+  pub fn with_tip(mut self, tip: Tip) -> Bill {
+    self.tip = match tip {
+      Tip::Percent(percent) => self.subtotal().percent(percent),
+      Tip::Amount(amount) => amount,
+    };
+    self
+  }
+
+  // Customers with a higher tier in the loyalty program pay less
+  pub fn with_loyalty_discount(mut self, tier: Tier) -> Bill {
+    self.discount_percent = tier.discount_percent();
+    self
+  }
+
+  pub fn split_between(mut self, guests: NonZeroU32) -> Bill {
+    self.guests = guests;
+    self
+  }
+
+  pub fn subtotal(&self) -> Cents {
+    self.lines.iter().map(BillLine::total).sum()
+  }
+
+  pub fn discount(&self) -> Cents {
+    self.subtotal().percent(self.discount_percent)
+  }
+
+  // Taxes are paid on what the customer pays, so after the discount
+  pub fn tax(&self) -> Cents {
+    (self.subtotal() - self.discount()).percent(self.tax_rate)
+  }
+
+  pub fn total(&self) -> Cents {
+    self.subtotal() - self.discount() + self.tax() + self.tip
+  }
+
+  // What each guest pays. When the total can't be split evenly, the first guests pay one cent more,
+  // so that the shares always add up to the total
+  pub fn shares(&self) -> Vec<Cents> {
+    let total = self.total().0;
+    let guests = u64::from(self.guests.get());
+    let share = total / guests;
+    let remainder = total % guests;
+    (0..guests)
+      .map(|guest| Cents(if guest < remainder { share + 1 } else { share }))
+      .collect()
+  }
+}
+
+const RECEIPT_WIDTH: usize = 32;
+
+// The receipt, ready to be printed
+impl fmt::Display for Bill {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let separator = "-".repeat(RECEIPT_WIDTH);
+    // Label on the left, amount on the right
+    let row = |label: String, amount: String| format!("{label:<22}{amount:>10}");
+
+    writeln!(f, "Table {}", self.table)?;
+    for line in &self.lines {
+      writeln!(f, "{}", row(format!("{} x {}", line.quantity, line.name), line.total().to_string()))?;
+    }
+    writeln!(f, "{separator}")?;
+    writeln!(f, "{}", row(String::from("Subtotal"), self.subtotal().to_string()))?;
+    if self.discount_percent > 0 {
+      writeln!(f, "{}", row(format!("Discount ({}%)", self.discount_percent), format!("-{}", self.discount())))?;
+    }
+    writeln!(f, "{}", row(format!("Tax ({}%)", self.tax_rate), self.tax().to_string()))?;
+    if self.tip > Cents(0) {
+      writeln!(f, "{}", row(String::from("Tip"), self.tip.to_string()))?;
+    }
+    writeln!(f, "{separator}")?;
+    write!(f, "{}", row(String::from("Total"), self.total().to_string()))?;
+
+    if self.guests.get() > 1 {
+      let shares: Vec<String> = self.shares().iter().map(Cents::to_string).collect();
+      write!(f, "\nSplit between {} guests: {}", self.guests, shares.join(" + "))?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::back_of_house::menu::{Course, MenuItem};
+
+  fn order() -> Order {
+    let mut order = Order::new(4);
+    order.add_item(MenuItem::new("Soup", Cents(500), Course::Starter)).unwrap();
+    order.add_item(MenuItem::new("Salmon", Cents(1800), Course::Main)).unwrap();
+    order.add_item(MenuItem::new("Soup", Cents(500), Course::Starter)).unwrap();
+    order
+  }
+
+  #[test]
+  fn formats_cents_as_units_and_cents() {
+    assert_eq!(Cents(1250).to_string(), "12.50");
+    assert_eq!(Cents(7).to_string(), "0.07");
+    assert_eq!(format!("{:>6}", Cents(100)), "  1.00");
+  }
+
+  #[test]
+  fn rounds_percentages_to_the_nearest_cent() {
+    assert_eq!(Cents(1000).percent(21), Cents(210));
+    assert_eq!(Cents(250).percent(21), Cents(53));
+    assert_eq!(Cents(249).percent(10), Cents(25));
+  }
+
+  #[test]
+  fn large_amounts_dont_overflow() {
+    assert_eq!(Cents(50_000_000).percent(100), Cents(50_000_000));
+    assert_eq!(Cents(5_000_000_000) + Cents(5_000_000_000), Cents(10_000_000_000));
+    assert_eq!(Cents(2_000_000_000) * 3, Cents(6_000_000_000));
+    assert_eq!(Cents(6_000_000_000) - Cents(5_000_000_000), Cents(1_000_000_000));
+
+    let bill = Bill::for_order(&order(), 10).with_tip(Tip::Percent(u32::MAX));
+    assert_eq!(bill.tip, Cents(120_259_084_260));
+  }
+
+  #[test]
+  #[should_panic(expected = "amount of money can't be negative")]
+  fn amounts_cant_go_below_zero() {
+    let _ = Cents(1) - Cents(2);
+  }
+
+  #[test]
+  fn groups_repeated_items_in_one_line() {
+    let bill = Bill::for_order(&order(), 10);
+
+    assert_eq!(bill.lines.len(), 2);
+    assert_eq!(bill.lines[0], BillLine { name: String::from("Soup"), quantity: 2, unit_price: Cents(500) });
+    assert_eq!(bill.subtotal(), Cents(2800));
+    assert_eq!(bill.tax(), Cents(280));
+    assert_eq!(bill.total(), Cents(3080));
+  }
+
+  #[test]
+  fn adds_the_tip() {
+    assert_eq!(Bill::for_order(&order(), 10).with_tip(Tip::Percent(15)).tip, Cents(420));
+    assert_eq!(Bill::for_order(&order(), 10).with_tip(Tip::Amount(Cents(300))).total(), Cents(3380));
+  }
+
+  #[test]
+  fn applies_the_loyalty_discount_before_taxes() {
+    let bill = Bill::for_order(&order(), 10).with_loyalty_discount(Tier::Gold);
+
+    assert_eq!(bill.discount(), Cents(280));
+    assert_eq!(bill.tax(), Cents(252));
+    assert_eq!(bill.total(), Cents(2772));
+    assert!(bill.to_string().contains("\nDiscount (10%)             -2.80\n"));
+    assert_eq!(Bill::for_order(&order(), 10).with_loyalty_discount(Tier::Bronze).total(), Cents(3080));
+  }
+
+  #[test]
+  fn shares_add_up_to_the_total() {
+    let bill = Bill::for_order(&order(), 10).with_tip(Tip::Amount(Cents(1))).split_between(NonZeroU32::new(3).unwrap());
+
+    assert_eq!(bill.shares(), vec![Cents(1027), Cents(1027), Cents(1027)]);
+    let bill = bill.with_tip(Tip::Amount(Cents(0)));
+    assert_eq!(bill.shares(), vec![Cents(1027), Cents(1027), Cents(1026)]);
+  }
+
+  #[test]
+  fn renders_the_receipt() {
+    let bill = Bill::for_order(&order(), 10).with_tip(Tip::Amount(Cents(320))).split_between(NonZeroU32::new(2).unwrap());
+
+    assert_eq!(
+      bill.to_string(),
+      "Table 4\n\
+       2 x Soup                   10.00\n\
+       1 x Salmon                 18.00\n\
+       --------------------------------\n\
+       Subtotal                   28.00\n\
+       Tax (10%)                   2.80\n\
+       Tip                         3.20\n\
+       --------------------------------\n\
+       Total                      34.00\n\
+       Split between 2 guests: 17.00 + 17.00"
+    );
+  }
+}
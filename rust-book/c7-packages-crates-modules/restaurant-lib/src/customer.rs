@@ -1,5 +1,12 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
 // Make "Client" available without having to reference its path
 use crate::back_of_house::Client;
+use crate::billing::{Bill, Cents};
+use crate::front_of_house::orders::{Order, OrderId, OrderStatus};
 
 pub fn book_at_restaurant(name: &str, phone: &str) {
   let client = Client {
@@ -8,5 +15,236 @@ pub fn book_at_restaurant(name: &str, phone: &str) {
   };
   println!("Client {:?} wants to book a table", client);
   // Appetizer is out of scope because it's declared at root, not in-module
-  let free_appetizer = crate::Appetizer::FreeAppetizerOnTheHouse;
-}
\ No newline at end of file
+  let _free_appetizer = crate::Appetizer::FreeAppetizerOnTheHouse;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Tier {
+  // Declared from lowest to highest, so that the derived `Ord` gives `Bronze < Silver < Gold`
+  Bronze,
+  Silver,
+  Gold,
+}
+
+impl Tier {
+  pub fn for_points(points: u32) -> Tier {
+    match points {
+      0..=99 => Tier::Bronze,
+      100..=499 => Tier::Silver,
+      _ => Tier::Gold,
+    }
+  }
+
+  // Applied to the subtotal of the bill, before taxes
+  pub fn discount_percent(self) -> u32 {
+    match self {
+      Tier::Bronze => 0,
+      Tier::Silver => 5,
+      Tier::Gold => 10,
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Visit {
+  // The paid order, so that it can't be recorded twice
+  pub order: OrderId,
+  pub table: u8,
+  // What counts for the points: the subtotal after the loyalty discount
+  pub spent: Cents,
+  pub points: u32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Customer {
+  pub client: Client,
+  pub visits: Vec<Visit>,
+  pub points: u32,
+}
+
+impl Customer {
+  pub fn tier(&self) -> Tier {
+    Tier::for_points(self.points)
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CustomerError {
+  // Customers are identified by their phone number
+  UnknownCustomer(String),
+  // Only paid orders give points
+  NotPaid(OrderStatus),
+  // Each order gives points only once
+  AlreadyRecorded(OrderId),
+}
+
+impl fmt::Display for CustomerError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      CustomerError::UnknownCustomer(phone) => write!(f, "no customer with phone {phone}"),
+      CustomerError::NotPaid(status) => write!(f, "the order is {status:?}, not paid"),
+      CustomerError::AlreadyRecorded(id) => write!(f, "order #{id} was already recorded"),
+    }
+  }
+}
+
+// The loyalty program: every paid order gives one point per whole unit of its subtotal after the
+// loyalty discount. Taxes and tips don't count, since they aren't spent on the restaurant
+// Saved with the rest of the state (see `persistence`), so that the points survive a restart
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CustomerRegistry {
+  // By phone number. A `BTreeMap` keeps the saved file in the same order every time
+  customers: BTreeMap<String, Customer>,
+}
+
+impl CustomerRegistry {
+  pub fn new() -> CustomerRegistry {
+    CustomerRegistry::default()
+  }
+
+  // Registering a phone number that is already known keeps its history
+  pub fn register(&mut self, client: Client) -> &Customer {
+    self.customers.entry(client.phone.clone()).or_insert(Customer {
+      client,
+      visits: Vec::new(),
+      points: 0,
+    })
+  }
+
+  pub fn get(&self, phone: &str) -> Option<&Customer> {
+    self.customers.get(phone)
+  }
+
+  // Unknown customers (e.g. walk-ins) have no discount nor priority
+  pub fn tier(&self, phone: &str) -> Tier {
+    self.get(phone).map_or(Tier::Bronze, Customer::tier)
+  }
+
+  // Adds the visit to the history of the customer, and returns the points earned.
+  // `id` is the one the order had in the `OrderBook` (`take_payment` closes the order, but the caller knows it).
+  // The discount is the one of the tier the customer had when paying
+  pub fn record_paid_order(&mut self, phone: &str, id: OrderId, order: &Order) -> Result<u32, CustomerError> {
+    if self.customers.values().flat_map(|customer| &customer.visits).any(|visit| visit.order == id) {
+      return Err(CustomerError::AlreadyRecorded(id));
+    }
+    let customer = self
+      .customers
+      .get_mut(phone)
+      .ok_or_else(|| CustomerError::UnknownCustomer(String::from(phone)))?;
+    if order.status() != OrderStatus::Paid {
+      return Err(CustomerError::NotPaid(order.status()));
+    }
+
+    let bill = Bill::for_order(order, 0).with_loyalty_discount(customer.tier());
+    let spent = bill.subtotal() - bill.discount();
+    let points = u32::try_from(spent.0 / 100).unwrap_or(u32::MAX);
+    customer.visits.push(Visit { order: id, table: order.table, spent, points });
+    customer.points = customer.points.saturating_add(points);
+    Ok(points)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::back_of_house::menu::{Course, MenuItem};
+
+  fn client() -> Client {
+    Client { name: String::from("Miguel"), phone: String::from("080560992") }
+  }
+
+  // A tasting menu of 55.00, not paid yet
+  fn served_order() -> Order {
+    let mut order = Order::new(7);
+    order.add_item(MenuItem::new("Tasting menu", Cents(5500), Course::Main)).unwrap();
+    order.send_to_kitchen().unwrap();
+    order.serve().unwrap();
+    order
+  }
+
+  fn paid_order() -> Order {
+    let mut order = served_order();
+    order.pay().unwrap();
+    order
+  }
+
+  #[test]
+  fn tiers_go_up_with_points() {
+    assert_eq!(Tier::for_points(0), Tier::Bronze);
+    assert_eq!(Tier::for_points(100), Tier::Silver);
+    assert_eq!(Tier::for_points(500), Tier::Gold);
+    assert!(Tier::Gold > Tier::Silver && Tier::Silver > Tier::Bronze);
+  }
+
+  #[test]
+  fn paid_orders_add_visits_and_points() {
+    let mut registry = CustomerRegistry::new();
+    registry.register(client());
+
+    assert_eq!(registry.record_paid_order("080560992", 1, &paid_order()), Ok(55));
+    assert_eq!(registry.tier("080560992"), Tier::Bronze);
+    registry.record_paid_order("080560992", 2, &paid_order()).unwrap();
+
+    let customer = registry.get("080560992").unwrap();
+    assert_eq!(customer.points, 110);
+    assert_eq!(customer.visits.len(), 2);
+    assert_eq!(customer.visits[0], Visit { order: 1, table: 7, spent: Cents(5500), points: 55 });
+    assert_eq!(customer.tier(), Tier::Silver);
+  }
+
+  #[test]
+  fn points_are_earned_on_what_is_paid_after_the_discount() {
+    let mut registry = CustomerRegistry::new();
+    registry.register(client());
+    registry.record_paid_order("080560992", 1, &paid_order()).unwrap();
+    registry.record_paid_order("080560992", 2, &paid_order()).unwrap();
+
+    // Silver: 5% off 55.00 leaves 52.25
+    assert_eq!(registry.record_paid_order("080560992", 3, &paid_order()), Ok(52));
+    assert_eq!(registry.get("080560992").unwrap().visits[2].spent, Cents(5225));
+  }
+
+  #[test]
+  fn unpaid_orders_earn_nothing() {
+    let mut registry = CustomerRegistry::new();
+    registry.register(client());
+
+    assert_eq!(
+      registry.record_paid_order("080560992", 1, &served_order()),
+      Err(CustomerError::NotPaid(OrderStatus::Served))
+    );
+    assert!(registry.get("080560992").unwrap().visits.is_empty());
+  }
+
+  #[test]
+  fn orders_are_only_recorded_once() {
+    let mut registry = CustomerRegistry::new();
+    registry.register(client());
+    registry.register(Client { name: String::from("Ana"), phone: String::from("555") });
+    registry.record_paid_order("080560992", 1, &paid_order()).unwrap();
+
+    assert_eq!(registry.record_paid_order("080560992", 1, &paid_order()), Err(CustomerError::AlreadyRecorded(1)));
+    assert_eq!(registry.record_paid_order("555", 1, &paid_order()), Err(CustomerError::AlreadyRecorded(1)));
+    assert_eq!(registry.get("080560992").unwrap().points, 55);
+  }
+
+  #[test]
+  fn registering_again_keeps_the_history() {
+    let mut registry = CustomerRegistry::new();
+    registry.register(client());
+    registry.record_paid_order("080560992", 1, &paid_order()).unwrap();
+
+    assert_eq!(registry.register(client()).points, 55);
+  }
+
+  #[test]
+  fn unknown_customers_earn_nothing() {
+    let mut registry = CustomerRegistry::new();
+
+    assert_eq!(
+      registry.record_paid_order("555", 1, &paid_order()),
+      Err(CustomerError::UnknownCustomer(String::from("555")))
+    );
+    assert_eq!(registry.tier("555"), Tier::Bronze);
+  }
+}
@@ -1,3 +1,5 @@
 pub mod hosting;
 
-pub mod serving;
\ No newline at end of file
+pub mod orders;
+
+pub mod serving;
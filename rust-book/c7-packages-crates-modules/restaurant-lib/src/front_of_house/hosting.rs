@@ -1,6 +1,8 @@
 mod table;
 
 use table::{occupy_table, search_free_table};
+use crate::back_of_house::Client;
+use crate::customer::Tier;
 
 pub fn add_to_waitlist() {}
 
@@ -10,10 +12,63 @@ pub fn seat_at_table() -> bool {
   match table {
     Some(table_id) => {
       occupy_table(table_id);
-      return true;
+      true
     },
-    None => {
-      return false;
-    }
+    None => false
+  }
+}
+
+// Parties waiting for a table. Customers with a higher loyalty tier are seated first,
+// and customers of the same tier in the order they arrived
+#[derive(Debug, Default)]
+pub struct Waitlist {
+  parties: Vec<(Client, Tier)>,
+}
+
+impl Waitlist {
+  pub fn new() -> Waitlist {
+    Waitlist::default()
+  }
+
+  // The tier usually comes from the customer registry: `registry.tier(&client.phone)`
+  pub fn add(&mut self, client: Client, tier: Tier) {
+    self.parties.push((client, tier));
+  }
+
+  pub fn len(&self) -> usize {
+    self.parties.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.parties.is_empty()
+  }
+
+  // Removes and returns the party that should be seated next
+  pub fn seat_next(&mut self) -> Option<Client> {
+    let best = self.parties.iter().map(|(_, tier)| *tier).max()?;
+    let index = self.parties.iter().position(|(_, tier)| *tier == best)?;
+    Some(self.parties.remove(index).0)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn client(name: &str) -> Client {
+    Client { name: String::from(name), phone: format!("{name}-phone") }
+  }
+
+  #[test]
+  fn seats_higher_tiers_first_then_by_arrival() {
+    let mut waitlist = Waitlist::new();
+    waitlist.add(client("Ana"), Tier::Bronze);
+    waitlist.add(client("Bea"), Tier::Silver);
+    waitlist.add(client("Carl"), Tier::Gold);
+    waitlist.add(client("Dani"), Tier::Silver);
+
+    let order: Vec<String> = std::iter::from_fn(|| waitlist.seat_next()).map(|client| client.name).collect();
+    assert_eq!(order, vec!["Carl", "Bea", "Dani", "Ana"]);
+    assert!(waitlist.is_empty());
   }
 }
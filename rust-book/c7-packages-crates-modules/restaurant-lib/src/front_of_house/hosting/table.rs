@@ -2,12 +2,12 @@ pub fn search_free_table() -> Option<u8> {
   let there_are_free_tables = true;
 
   if there_are_free_tables {
-    return None
+    None
   } else {
-    return Some(5)
+    Some(5)
   }
 }
 
-pub fn occupy_table(table_id: u8) {
+pub fn occupy_table(_table_id: u8) {
   // occupy table
 }
\ No newline at end of file
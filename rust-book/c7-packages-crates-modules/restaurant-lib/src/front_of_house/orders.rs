@@ -0,0 +1,261 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::back_of_house::inventory::InventoryError;
+use crate::back_of_house::menu::MenuItem;
+
+pub type OrderId = u32;
+
+// The states of an order, in the only order they can happen
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum OrderStatus {
+  Placed,
+  InKitchen,
+  Served,
+  Paid,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum OrderError {
+  // E.g. serving an order that the kitchen hasn't received yet
+  InvalidTransition { from: OrderStatus, to: OrderStatus },
+  // Items can only be added before the order is sent to the kitchen
+  AlreadyInKitchen,
+  EmptyOrder,
+  UnknownOrder(OrderId),
+  // Only paid orders can be closed
+  NotPaid(OrderId),
+  // The kitchen can't make some of the items
+  Inventory(InventoryError),
+}
+
+impl fmt::Display for OrderError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      OrderError::InvalidTransition { from, to } => write!(f, "an order can't go from {from:?} to {to:?}"),
+      OrderError::AlreadyInKitchen => write!(f, "the order was already sent to the kitchen"),
+      OrderError::EmptyOrder => write!(f, "the order has no items"),
+      OrderError::UnknownOrder(id) => write!(f, "there is no open order #{id}"),
+      OrderError::NotPaid(id) => write!(f, "order #{id} hasn't been paid yet"),
+      OrderError::Inventory(err) => write!(f, "the kitchen can't make the order: {err}"),
+    }
+  }
+}
+
+// Allows using `?` on inventory operations in functions that return an `OrderError`
+impl From<InventoryError> for OrderError {
+  fn from(err: InventoryError) -> OrderError {
+    OrderError::Inventory(err)
+  }
+}
+
+// Read through `UncheckedOrder`, so that a file can't give an order that the methods would never allow
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedOrder")]
+pub struct Order {
+  pub table: u8,
+  // Private: they can only change through the methods, which check the status
+  items: Vec<MenuItem>,
+  status: OrderStatus,
+}
+
+// The fields of an `Order` as they are in a file, before checking them
+#[derive(Deserialize)]
+struct UncheckedOrder {
+  table: u8,
+  items: Vec<MenuItem>,
+  status: OrderStatus,
+}
+
+// `send_to_kitchen` refuses empty orders, so only a `Placed` order can have no items
+impl TryFrom<UncheckedOrder> for Order {
+  type Error = OrderError;
+
+  fn try_from(unchecked: UncheckedOrder) -> Result<Order, OrderError> {
+    if unchecked.items.is_empty() && unchecked.status != OrderStatus::Placed {
+      return Err(OrderError::EmptyOrder);
+    }
+    Ok(Order { table: unchecked.table, items: unchecked.items, status: unchecked.status })
+  }
+}
+
+impl Order {
+  pub fn new(table: u8) -> Order {
+    Order {
+      table,
+      items: Vec::new(),
+      status: OrderStatus::Placed,
+    }
+  }
+
+  pub fn items(&self) -> &[MenuItem] {
+    &self.items
+  }
+
+  pub fn status(&self) -> OrderStatus {
+    self.status
+  }
+
+  pub fn add_item(&mut self, item: MenuItem) -> Result<(), OrderError> {
+    if self.status != OrderStatus::Placed {
+      return Err(OrderError::AlreadyInKitchen);
+    }
+    self.items.push(item);
+    Ok(())
+  }
+
+  pub fn send_to_kitchen(&mut self) -> Result<(), OrderError> {
+    if self.items.is_empty() {
+      return Err(OrderError::EmptyOrder);
+    }
+    self.advance(OrderStatus::Placed, OrderStatus::InKitchen)
+  }
+
+  pub fn serve(&mut self) -> Result<(), OrderError> {
+    self.advance(OrderStatus::InKitchen, OrderStatus::Served)
+  }
+
+  pub fn pay(&mut self) -> Result<(), OrderError> {
+    self.advance(OrderStatus::Served, OrderStatus::Paid)
+  }
+
+  // Moves to `to` only if the order is currently in `from`
+  fn advance(&mut self, from: OrderStatus, to: OrderStatus) -> Result<(), OrderError> {
+    if self.status != from {
+      return Err(OrderError::InvalidTransition { from: self.status, to });
+    }
+    self.status = to;
+    Ok(())
+  }
+}
+
+// The orders that haven't been closed yet, by id.
+// A `BTreeMap` keeps them sorted by id, which is also the order in which they were placed
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "UncheckedOrderBook")]
+pub struct OrderBook {
+  orders: BTreeMap<OrderId, Order>,
+  // The last id given. Placing an order with an id that is already used would replace the old order
+  next_id: OrderId,
+}
+
+// The fields of an `OrderBook` as they are in a file, before checking them
+#[derive(Deserialize)]
+struct UncheckedOrderBook {
+  orders: BTreeMap<OrderId, Order>,
+  next_id: OrderId,
+}
+
+// A `next_id` below the ids of the orders (e.g. edited by hand) is moved past them
+impl From<UncheckedOrderBook> for OrderBook {
+  fn from(unchecked: UncheckedOrderBook) -> OrderBook {
+    let last_id = unchecked.orders.keys().next_back().copied().unwrap_or(0);
+    OrderBook { next_id: unchecked.next_id.max(last_id), orders: unchecked.orders }
+  }
+}
+
+impl OrderBook {
+  pub fn new() -> OrderBook {
+    OrderBook::default()
+  }
+
+  pub fn place(&mut self, order: Order) -> OrderId {
+    self.next_id += 1;
+    self.orders.insert(self.next_id, order);
+    self.next_id
+  }
+
+  pub fn get(&self, id: OrderId) -> Result<&Order, OrderError> {
+    self.orders.get(&id).ok_or(OrderError::UnknownOrder(id))
+  }
+
+  pub fn get_mut(&mut self, id: OrderId) -> Result<&mut Order, OrderError> {
+    self.orders.get_mut(&id).ok_or(OrderError::UnknownOrder(id))
+  }
+
+  // Removes a paid order from the book and gives it back (e.g. to print the receipt)
+  pub fn close(&mut self, id: OrderId) -> Result<Order, OrderError> {
+    if self.get(id)?.status != OrderStatus::Paid {
+      return Err(OrderError::NotPaid(id));
+    }
+    Ok(self.orders.remove(&id).expect("the order exists"))
+  }
+
+  pub fn open_orders(&self) -> Vec<(OrderId, &Order)> {
+    self.orders.iter().map(|(id, order)| (*id, order)).collect()
+  }
+
+  pub fn for_table(&self, table: u8) -> Vec<(OrderId, &Order)> {
+    self.open_orders().into_iter().filter(|(_, order)| order.table == table).collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::back_of_house::menu::Course;
+  use crate::billing::Cents;
+
+  fn soup() -> MenuItem {
+    MenuItem::new("Soup", Cents(500), Course::Starter)
+  }
+
+  #[test]
+  fn goes_through_all_the_states() {
+    let mut order = Order::new(3);
+    order.add_item(soup()).unwrap();
+
+    order.send_to_kitchen().unwrap();
+    assert_eq!(order.status(), OrderStatus::InKitchen);
+    order.serve().unwrap();
+    assert_eq!(order.status(), OrderStatus::Served);
+    order.pay().unwrap();
+    assert_eq!(order.status(), OrderStatus::Paid);
+  }
+
+  #[test]
+  fn rejects_invalid_transitions() {
+    let mut order = Order::new(3);
+    order.add_item(soup()).unwrap();
+
+    assert_eq!(order.serve(), Err(OrderError::InvalidTransition { from: OrderStatus::Placed, to: OrderStatus::Served }));
+    assert_eq!(order.pay(), Err(OrderError::InvalidTransition { from: OrderStatus::Placed, to: OrderStatus::Paid }));
+    order.send_to_kitchen().unwrap();
+    assert!(order.send_to_kitchen().is_err());
+    assert_eq!(order.status(), OrderStatus::InKitchen);
+  }
+
+  #[test]
+  fn items_can_only_be_added_before_sending_to_the_kitchen() {
+    let mut order = Order::new(3);
+    assert_eq!(order.send_to_kitchen(), Err(OrderError::EmptyOrder));
+
+    order.add_item(soup()).unwrap();
+    order.send_to_kitchen().unwrap();
+    assert_eq!(order.add_item(soup()), Err(OrderError::AlreadyInKitchen));
+    assert_eq!(order.items().len(), 1);
+  }
+
+  #[test]
+  fn order_book_tracks_open_orders() {
+    let mut book = OrderBook::new();
+    let first = book.place(Order::new(1));
+    let second = book.place(Order::new(2));
+    book.place(Order::new(1));
+
+    assert_eq!(book.open_orders().len(), 3);
+    assert_eq!(book.for_table(1).len(), 2);
+    assert_eq!(book.close(first), Err(OrderError::NotPaid(first)));
+
+    let order = book.get_mut(second).unwrap();
+    order.add_item(soup()).unwrap();
+    order.send_to_kitchen().unwrap();
+    order.serve().unwrap();
+    order.pay().unwrap();
+    assert_eq!(book.close(second).unwrap().table, 2);
+    assert_eq!(book.get(second), Err(OrderError::UnknownOrder(second)));
+    assert_eq!(book.open_orders().len(), 2);
+  }
+}
@@ -1,5 +1,79 @@
-fn take_order() {}
+use crate::back_of_house::inventory::Inventory;
+use crate::back_of_house::menu::MenuItem;
+use super::orders::{Order, OrderBook, OrderError, OrderId};
 
-fn serve_order() {}
+// Writes down what a table wants and sends it straight to the kitchen, which takes the ingredients.
+// If some ingredient is missing, the whole order is refused and the stock is left untouched
+pub fn take_order(
+  book: &mut OrderBook,
+  inventory: &mut Inventory,
+  table: u8,
+  items: Vec<MenuItem>,
+) -> Result<OrderId, OrderError> {
+  let mut order = Order::new(table);
+  for item in items {
+    order.add_item(item)?;
+  }
+  order.send_to_kitchen()?;
+  inventory.consume(order.items())?;
+  Ok(book.place(order))
+}
 
-fn take_payment() {}
\ No newline at end of file
+pub fn serve_order(book: &mut OrderBook, id: OrderId) -> Result<(), OrderError> {
+  book.get_mut(id)?.serve()
+}
+
+// Once paid, the order is no longer open
+pub fn take_payment(book: &mut OrderBook, id: OrderId) -> Result<Order, OrderError> {
+  book.get_mut(id)?.pay()?;
+  book.close(id)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::back_of_house::inventory::InventoryError;
+  use crate::back_of_house::menu::Course;
+  use crate::billing::Cents;
+  use crate::front_of_house::orders::OrderStatus;
+
+  fn soup() -> MenuItem {
+    MenuItem::new("Soup", Cents(500), Course::Starter)
+  }
+
+  fn inventory() -> Inventory {
+    let mut inventory = Inventory::new();
+    inventory.restock("tomatoes", 6, 1);
+    inventory.set_recipe("Soup", &[("tomatoes", 3)]);
+    inventory
+  }
+
+  #[test]
+  fn orders_are_open_until_paid() {
+    let mut book = OrderBook::new();
+    let mut inventory = inventory();
+    let id = take_order(&mut book, &mut inventory, 4, vec![soup()]).unwrap();
+    assert_eq!(book.get(id).unwrap().status(), OrderStatus::InKitchen);
+
+    assert!(take_payment(&mut book, id).is_err());
+    serve_order(&mut book, id).unwrap();
+    assert_eq!(take_payment(&mut book, id).unwrap().status(), OrderStatus::Paid);
+    assert!(book.open_orders().is_empty());
+    assert_eq!(take_order(&mut book, &mut inventory, 4, vec![]), Err(OrderError::EmptyOrder));
+  }
+
+  #[test]
+  fn orders_are_refused_when_ingredients_run_out() {
+    let mut book = OrderBook::new();
+    let mut inventory = inventory();
+    take_order(&mut book, &mut inventory, 1, vec![soup()]).unwrap();
+
+    let err = take_order(&mut book, &mut inventory, 2, vec![soup(), soup()]).unwrap_err();
+    assert_eq!(
+      err,
+      OrderError::Inventory(InventoryError::OutOfStock { ingredient: String::from("tomatoes"), needed: 6, available: 3 })
+    );
+    assert_eq!(inventory.quantity("tomatoes"), 3);
+    assert_eq!(book.open_orders().len(), 1);
+  }
+}
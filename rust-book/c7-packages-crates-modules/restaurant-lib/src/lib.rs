@@ -1,36 +1,19 @@
-mod back_of_house {
-  pub struct Breakfast {
-    pub toast: String,
-    seasonal_fruit: String,
-  }
-
-  impl Breakfast {
-    pub fn summer(toast: &str) -> Breakfast {
-      Breakfast {
-        toast: String::from(toast),
-        seasonal_fruit: String::from("peaches"),
-      }
-    }
-  }
-
-  pub enum Appetizer {
-    Soup,
-    Salad,
-    FreeAppetizerOnTheHouse
-  }
-
-  #[derive(Debug)]
-  pub struct Client {
-    pub name: String,
-    pub phone: String
-  }
-}
-
+// The kitchen (and its menu) is public, so that users of the library can build their own menus
+pub mod back_of_house;
+pub mod billing;
 mod customer;
-mod front_of_house;
+pub mod front_of_house;
+pub mod persistence;
 
 use crate::back_of_house::Appetizer;
 
+// Re-export the main types, so that they can be used as `restaurant_lib::Menu` (and not `restaurant_lib::back_of_house::menu::Menu`)
+pub use back_of_house::inventory::{Inventory, InventoryError};
+pub use back_of_house::menu::{Allergen, Course, Menu, MenuError, MenuItem, Season};
+pub use billing::{Bill, Cents, Tip};
+pub use front_of_house::orders::{Order, OrderBook, OrderError, OrderId, OrderStatus};
+pub use persistence::{PersistenceError, RestaurantState};
+
 pub fn eat_at_restaurant() {
   // Absolute path
   crate::front_of_house::hosting::add_to_waitlist();
@@ -49,17 +32,13 @@ pub fn eat_at_restaurant() {
   // meal.seasonal_fruit = String::from("blueberries");
 
   // Enums' values are public if the enum is "pub"
-  let order1 = back_of_house::Appetizer::Soup;
-  let order2 = back_of_house::Appetizer::Salad;
+  // (the `_` prefix tells the compiler that the variables are unused on purpose)
+  let _order1 = back_of_house::Appetizer::Soup;
+  let _order2 = back_of_house::Appetizer::Salad;
 }
 
 // Re-export: allows external users of the restaurant to call Restaurant::book_at_restaurant
 // Without having to know about our internal code organisation (Restaurant::customer::book_at_restaurant)
 pub use customer::book_at_restaurant;
-
-use rand::Rng;
-
-fn main() {
-    let secret_number = rand::thread_rng().gen_range(1..=100);
-    book_at_restaurant("foo", "bar");
-}
\ No newline at end of file
+// Same for the loyalty program, which also lives in the private `customer` module
+pub use customer::{Customer, CustomerError, CustomerRegistry, Tier, Visit};
@@ -0,0 +1,197 @@
+use std::fmt;
+use std::fs;
+use std::io;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::back_of_house::menu::Menu;
+use crate::back_of_house::Client;
+use crate::customer::CustomerRegistry;
+use crate::front_of_house::orders::OrderBook;
+
+// Increase it whenever the structure of the saved state changes, so that old files are not misread
+pub const FORMAT_VERSION: u32 = 2;
+
+// Everything that has to survive a restart of the restaurant
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RestaurantState {
+  pub menu: Menu,
+  // Only open orders: paid ones are removed from the book
+  pub orders: OrderBook,
+  // Clients that booked a table
+  pub reservations: Vec<Client>,
+  // The loyalty program, with the points of every customer
+  pub customers: CustomerRegistry,
+}
+
+// What is actually written to the file: the state with the version of the format next to it.
+// `flatten` puts the fields of the state at the same level as `version`, instead of in a nested object
+#[derive(Serialize)]
+struct VersionedState<'a> {
+  version: u32,
+  #[serde(flatten)]
+  state: &'a RestaurantState,
+}
+
+#[derive(Debug)]
+pub enum PersistenceError {
+  Io(io::Error),
+  Json(serde_json::Error),
+  // The file was written by another version of the library. `None` if it had no version at all
+  UnsupportedVersion(Option<u64>),
+}
+
+impl fmt::Display for PersistenceError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      PersistenceError::Io(err) => write!(f, "could not access the file: {err}"),
+      PersistenceError::Json(err) => write!(f, "invalid JSON: {err}"),
+      PersistenceError::UnsupportedVersion(Some(version)) => {
+        write!(f, "unsupported format version {version} (expected {FORMAT_VERSION})")
+      }
+      PersistenceError::UnsupportedVersion(None) => write!(f, "the file has no format version"),
+    }
+  }
+}
+
+impl From<io::Error> for PersistenceError {
+  fn from(err: io::Error) -> PersistenceError {
+    PersistenceError::Io(err)
+  }
+}
+
+impl From<serde_json::Error> for PersistenceError {
+  fn from(err: serde_json::Error) -> PersistenceError {
+    PersistenceError::Json(err)
+  }
+}
+
+pub fn to_json(state: &RestaurantState) -> Result<String, PersistenceError> {
+  let versioned = VersionedState { version: FORMAT_VERSION, state };
+  Ok(serde_json::to_string_pretty(&versioned)?)
+}
+
+// The version is checked before reading the rest, so that a file of another version
+// gives a clear error instead of a confusing "missing field"
+pub fn from_json(json: &str) -> Result<RestaurantState, PersistenceError> {
+  let value: Value = serde_json::from_str(json)?;
+  let version = value.get("version").and_then(Value::as_u64);
+  if version != Some(FORMAT_VERSION.into()) {
+    return Err(PersistenceError::UnsupportedVersion(version));
+  }
+  // Unknown fields (like "version") are ignored
+  Ok(serde_json::from_value(value)?)
+}
+
+pub fn save(path: &str, state: &RestaurantState) -> Result<(), PersistenceError> {
+  fs::write(path, to_json(state)?)?;
+  Ok(())
+}
+
+pub fn load(path: &str) -> Result<RestaurantState, PersistenceError> {
+  from_json(&fs::read_to_string(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::back_of_house::menu::{Allergen, Course, MenuItem};
+  use crate::billing::Cents;
+  use crate::front_of_house::orders::Order;
+
+  fn state() -> RestaurantState {
+    let mut menu = Menu::new();
+    menu.add(MenuItem::new("Soup", Cents(500), Course::Starter)).unwrap();
+    menu.add(MenuItem::new("Salmon", Cents(1800), Course::Main).with_allergens(&[Allergen::Fish])).unwrap();
+    menu.set_daily_specials(&["Salmon"]).unwrap();
+
+    let mut orders = OrderBook::new();
+    let mut order = Order::new(2);
+    order.add_item(menu.get("Soup").unwrap().clone()).unwrap();
+    order.send_to_kitchen().unwrap();
+    orders.place(order);
+
+    let client = Client { name: String::from("Miguel"), phone: String::from("080560992") };
+    let mut customers = CustomerRegistry::new();
+    customers.register(client.clone());
+    let mut paid = Order::new(1);
+    paid.add_item(menu.get("Salmon").unwrap().clone()).unwrap();
+    paid.send_to_kitchen().unwrap();
+    paid.serve().unwrap();
+    paid.pay().unwrap();
+    customers.record_paid_order(&client.phone, 7, &paid).unwrap();
+
+    RestaurantState { menu, orders, reservations: vec![client], customers }
+  }
+
+  #[test]
+  fn state_round_trips_through_json() {
+    let json = to_json(&state()).unwrap();
+
+    assert!(json.contains("\"version\": 2"));
+    assert!(json.contains("\"price\": 1800"));
+    let loaded = from_json(&json).unwrap();
+    assert_eq!(loaded.customers.get("080560992").unwrap().points, 18);
+    assert_eq!(loaded, state());
+  }
+
+  #[test]
+  fn rejects_other_versions() {
+    let json = to_json(&state()).unwrap().replace("\"version\": 2", "\"version\": 1");
+    assert!(matches!(from_json(&json), Err(PersistenceError::UnsupportedVersion(Some(1)))));
+
+    let json = r#"{"menu": {"items": [], "daily_specials": []}, "orders": {}, "reservations": []}"#;
+    assert!(matches!(from_json(json), Err(PersistenceError::UnsupportedVersion(None))));
+  }
+
+  #[test]
+  fn rejects_invalid_json() {
+    assert!(matches!(from_json("{\"version\": 2, \"menu\": 3}"), Err(PersistenceError::Json(_))));
+    assert!(matches!(from_json("not json"), Err(PersistenceError::Json(_))));
+  }
+
+  #[test]
+  fn rejects_menus_that_break_its_rules() {
+    let json = to_json(&state()).unwrap().replace("\"name\": \"Salmon\"", "\"name\": \"Soup\"");
+    let err = from_json(&json).unwrap_err();
+    assert!(err.to_string().contains("'Soup' is already on the menu"), "{err}");
+
+    let json = to_json(&state()).unwrap().replace("\"Salmon\"\n", "\"Pizza\"\n");
+    let err = from_json(&json).unwrap_err();
+    assert!(err.to_string().contains("'Pizza' is not on the menu"), "{err}");
+  }
+
+  #[test]
+  fn rejects_orders_in_the_kitchen_without_items() {
+    let mut state = state();
+    state.orders.place(Order::new(3));
+    let json = to_json(&state).unwrap();
+    assert!(from_json(&json).is_ok());
+
+    let json = json.replace("\"status\": \"Placed\"", "\"status\": \"InKitchen\"");
+    let err = from_json(&json).unwrap_err();
+    assert!(err.to_string().contains("the order has no items"), "{err}");
+  }
+
+  #[test]
+  fn new_orders_never_replace_loaded_ones() {
+    let json = to_json(&state()).unwrap().replace("\"next_id\": 1", "\"next_id\": 0");
+    assert!(json.contains("\"next_id\": 0"));
+    let mut state = from_json(&json).unwrap();
+
+    assert_eq!(state.orders.place(Order::new(5)), 2);
+    assert_eq!(state.orders.open_orders().len(), 2);
+  }
+
+  #[test]
+  fn saves_and_loads_files() {
+    let path = std::env::temp_dir().join(format!("restaurant-state-{}.json", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    save(path, &state()).unwrap();
+    assert_eq!(load(path).unwrap(), state());
+    fs::remove_file(path).unwrap();
+    assert!(matches!(load(path), Err(PersistenceError::Io(_))));
+  }
+}